@@ -0,0 +1,104 @@
+//! Compares per-variant operations on a coproduct with a native enum `match`.
+//!
+//! Run with `cargo bench`. Prints the average time per value for each
+//! operation on a mix of all variants.
+//!
+//! Run with `cargo bench --profile dev` to measure an unoptimised build.
+//! There the coproduct copies its whole table of functions on every
+//! dispatch instead of reading a single entry.
+
+use coproduct::Coproduct;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+macro_rules! variants {
+    ($($v:ident),+) => {
+        $(
+            #[derive(Clone, Debug, PartialEq)]
+            struct $v(String);
+        )+
+
+        #[derive(Clone, Debug, PartialEq)]
+        enum Native {
+            $($v($v)),+
+        }
+
+        type Sum = Coproduct!($($v),+);
+
+        fn values() -> (Vec<Native>, Vec<Sum>) {
+            let mut native = vec![];
+            let mut sum = vec![];
+            for i in 0..64 {
+                $(
+                    let s = format!("{}{}", stringify!($v), i);
+                    native.push(Native::$v($v(s.clone())));
+                    sum.push(Sum::inject($v(s)));
+                )+
+            }
+            (native, sum)
+        }
+    };
+}
+
+variants!(
+    A0, A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20,
+    A21, A22, A23, A24, A25, A26, A27, A28, A29, A30, A31
+);
+
+const ROUNDS: u32 = 200;
+
+fn time(len: usize, mut op: impl FnMut(usize)) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for i in 0..len {
+            op(black_box(i));
+        }
+    }
+    start.elapsed() / (ROUNDS * len as u32)
+}
+
+fn compare(name: &str, len: usize, native: impl FnMut(usize), sum: impl FnMut(usize)) {
+    let native = time(len, native);
+    let sum = time(len, sum);
+    println!("{name:<12} enum {native:>8?}   coproduct {sum:>8?}");
+}
+
+fn main() {
+    let profile = if cfg!(debug_assertions) {
+        "unoptimised"
+    } else {
+        "optimised"
+    };
+    println!("{profile} build");
+
+    let (native, sum) = values();
+    let (native_copy, sum_copy) = values();
+    let len = native.len();
+    let (mut native_out, mut sum_out) = (String::new(), String::new());
+
+    compare(
+        "clone+drop",
+        len,
+        |i| drop(black_box(native[i].clone())),
+        |i| drop(black_box(sum[i].clone())),
+    );
+    compare(
+        "eq",
+        len,
+        |i| assert!(black_box(native[i] == native_copy[i])),
+        |i| assert!(black_box(sum[i] == sum_copy[i])),
+    );
+    compare(
+        "debug",
+        len,
+        |i| {
+            native_out.clear();
+            write!(native_out, "{:?}", native[i]).unwrap();
+        },
+        |i| {
+            sum_out.clear();
+            write!(sum_out, "{:?}", sum[i]).unwrap();
+        },
+    );
+}
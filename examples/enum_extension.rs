@@ -9,6 +9,7 @@ struct C;
 #[derive(Debug)]
 struct D;
 
+#[allow(clippy::upper_case_acronyms)]
 type ABC = MkUnion!(A, B, C);
 
 fn main() {
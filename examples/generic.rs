@@ -1,28 +1,10 @@
-use coproduct::{At, Coproduct, CopyableCoproduct, Count, Embed, IndexedDrop, Union};
+use coproduct::{Coproduct, CopyableCoproduct, MapVariant};
 
-trait Prepend<T> {
-    type With;
-}
-
-impl<T, U: IndexedDrop> Prepend<T> for Coproduct<U> {
-    type With = Coproduct<Union<T, U>>;
-}
-
-impl<T: Copy, U: Copy> Prepend<T> for CopyableCoproduct<U> {
-    type With = CopyableCoproduct<Union<T, U>>;
-}
-
-fn transformer<C, I, J, Indices>(c: C) -> <<C as At<I, u8>>::Pruned as Prepend<u32>>::With
+fn transformer<C, I>(c: C) -> C::Mapped
 where
-    C: At<I, u8>,
-    C::Pruned: Prepend<u32> + Embed<<<C as At<I, u8>>::Pruned as Prepend<u32>>::With, Indices>,
-    <<C as At<I, u8>>::Pruned as Prepend<u32>>::With: At<J, u32>,
-    I: Count,
+    C: MapVariant<I, u8, u32>,
 {
-    match c.uninject() {
-        Ok(x) => coproduct::inject(x as u32),
-        Err(x) => x.embed(),
-    }
+    c.map_variant(u32::from)
 }
 
 fn main() {
@@ -32,7 +14,7 @@ fn main() {
     dbg!(y);
 
     let x: CopyableCoproduct!(i32, u8) = CopyableCoproduct::inject(8);
-    dbg!(x.clone());
+    dbg!(x);
     let y = transformer(x);
     dbg!(y);
 }
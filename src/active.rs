@@ -0,0 +1,93 @@
+use crate::{EmptyUnion, Union};
+
+/// A borrowed Union together with the index of its active variant.
+///
+/// Obtained from a borrowed [Coproduct](struct@crate::Coproduct) or
+/// [CopyableCoproduct](struct@crate::CopyableCoproduct) via [From]. It is the safe
+/// way of defining new operations that dispatch on the active variant, like
+/// the Indexed* traits do. Those take the index as an unchecked argument,
+/// whereas an Active can only be created with the correct index.
+/// ```
+/// use coproduct::{Active, Coproduct, EmptyUnion, Union};
+///
+/// trait ByteLen {
+///     fn byte_len(active: Active<&Self>) -> usize;
+/// }
+///
+/// impl<H: AsRef<[u8]>, T: ByteLen> ByteLen for Union<H, T> {
+///     fn byte_len(active: Active<&Self>) -> usize {
+///         match active.split() {
+///             Ok(head) => head.as_ref().len(),
+///             Err(tail) => T::byte_len(tail),
+///         }
+///     }
+/// }
+///
+/// impl ByteLen for EmptyUnion {
+///     fn byte_len(active: Active<&Self>) -> usize {
+///         active.ex_falso()
+///     }
+/// }
+///
+/// let c: Coproduct!(String, Vec<u8>) = Coproduct::inject(vec![1, 2, 3]);
+/// assert_eq!(ByteLen::byte_len(Active::from(&c)), 3);
+/// ```
+pub struct Active<P> {
+    union: P,
+    index: u32,
+}
+
+impl<P> Active<P> {
+    /// # Safety
+    /// `index` must be the index of the active variant of `union`.
+    pub(crate) unsafe fn new(union: P, index: u32) -> Self {
+        Active { union, index }
+    }
+
+    /// The index of the active variant.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl<'a, H, T> Active<&'a Union<H, T>> {
+    /// Returns the first variant if it is active. Otherwise, returns the
+    /// remaining variants.
+    pub fn split(self) -> Result<&'a H, Active<&'a T>> {
+        unsafe {
+            if self.index == 0 {
+                Ok(&self.union.head)
+            } else {
+                Err(Active::new(&self.union.tail, self.index - 1))
+            }
+        }
+    }
+}
+
+impl<'a, H, T> Active<&'a mut Union<H, T>> {
+    /// Returns the first variant if it is active. Otherwise, returns the
+    /// remaining variants.
+    pub fn split(self) -> Result<&'a mut H, Active<&'a mut T>> {
+        unsafe {
+            if self.index == 0 {
+                Ok(&mut self.union.head)
+            } else {
+                Err(Active::new(&mut self.union.tail, self.index - 1))
+            }
+        }
+    }
+}
+
+impl Active<&EmptyUnion> {
+    /// An empty Union cannot have an active variant.
+    pub fn ex_falso<R>(self) -> R {
+        match *self.union {}
+    }
+}
+
+impl Active<&mut EmptyUnion> {
+    /// An empty Union cannot have an active variant.
+    pub fn ex_falso<R>(self) -> R {
+        match *self.union {}
+    }
+}
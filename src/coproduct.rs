@@ -1,11 +1,20 @@
 use crate::{
-    count::Count,
+    active::Active,
+    count::{Count, Here, There},
+    matcher::Matcher,
     public_traits::*,
-    union::{union_transmute, IndexedClone, IndexedDebug, IndexedEq},
+    table::{lookup, Row},
+    tag::{Discriminant, TagFor},
+    union::{
+        union_transmute, IndexedClone, IndexedDebug, IndexedDisplay, IndexedDyn, IndexedEq,
+        IndexedError, IndexedFold, IndexedHash, IndexedInto, IndexedOrd, IndexedPartialOrd,
+        IndexedTotalEq, Pack, Substitute,
+    },
     EmptyUnion, Union,
 };
-use core::hint::unreachable_unchecked;
+use core::cmp::Ordering;
 use core::mem::ManuallyDrop;
+use core::num::NonZeroU8;
 
 #[cfg(feature = "type_inequality_hack")]
 use crate::Merge;
@@ -14,20 +23,47 @@ use crate::Merge;
 ///
 /// Do not use directly. Its only purpose is to avoid duplicating methods
 /// for Copy and non-Copy coproducts.
-struct LeakingCoproduct<T> {
-    tag: u32,
+struct LeakingCoproduct<T, Tag> {
+    tag: Tag,
     union: T,
 }
 
-impl<X: IndexedDebug> core::fmt::Debug for LeakingCoproduct<X> {
+impl<X: IndexedDebug, Tag: Discriminant> core::fmt::Debug for LeakingCoproduct<X, Tag> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        unsafe { self.union.ifmt(f, self.tag) }
+        unsafe { self.union.ifmt(f, self.tag.index()) }
     }
 }
 
-impl<T: IndexedEq> PartialEq for LeakingCoproduct<T> {
+impl<T: IndexedEq, Tag: Discriminant> PartialEq for LeakingCoproduct<T, Tag> {
     fn eq(&self, other: &Self) -> bool {
-        self.tag == other.tag && unsafe { self.union.ieq(&other.union, self.tag) }
+        self.tag == other.tag && unsafe { self.union.ieq(&other.union, self.tag.index()) }
+    }
+}
+
+impl<T: IndexedTotalEq, Tag: Discriminant> Eq for LeakingCoproduct<T, Tag> {}
+
+impl<T: IndexedHash, Tag: Discriminant> core::hash::Hash for LeakingCoproduct<T, Tag> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.tag.hash(state);
+        unsafe { self.union.ihash(state, self.tag.index()) }
+    }
+}
+
+/// Like derived implementations on enums, orders by variant first.
+impl<T: IndexedPartialOrd, Tag: Discriminant> PartialOrd for LeakingCoproduct<T, Tag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.tag.cmp(&other.tag) {
+            Ordering::Equal => unsafe { self.union.ipartial_cmp(&other.union, self.tag.index()) },
+            unequal => Some(unequal),
+        }
+    }
+}
+
+impl<T: IndexedOrd, Tag: Discriminant> Ord for LeakingCoproduct<T, Tag> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tag
+            .cmp(&other.tag)
+            .then_with(|| unsafe { self.union.icmp(&other.union, self.tag.index()) })
     }
 }
 
@@ -39,26 +75,27 @@ pub trait At<I, X> {
     type Pruned;
 }
 
-impl<I, X, U> At<I, X> for LeakingCoproduct<U>
+impl<I, X, U, Tag: Discriminant> At<I, X> for LeakingCoproduct<U, Tag>
 where
     U: UnionAt<I, X>,
     I: Count,
 {
     fn inject(x: X) -> Self {
         Self {
-            tag: I::count(),
+            tag: Tag::of::<I>(),
             union: U::inject(x),
         }
     }
 
     fn uninject(self) -> Result<X, Self::Pruned> {
-        if self.tag == I::count() {
+        if self.tag == Tag::of::<I>() {
             Ok(unsafe { self.union.take() })
         } else {
-            let tag = if self.tag < I::count() {
+            let tag = if self.tag < Tag::of::<I>() {
                 self.tag
             } else {
-                self.tag - 1
+                // Smaller than the current tag, so it fits.
+                unsafe { Tag::from_index_unchecked(self.tag.index() - 1) }
             };
             Err(LeakingCoproduct {
                 tag,
@@ -67,54 +104,99 @@ where
         }
     }
 
-    type Pruned = LeakingCoproduct<U::Pruned>;
+    type Pruned = LeakingCoproduct<U::Pruned, Tag>;
 }
 
-trait IndexList {
-    /// # Safety
-    /// Calling this function with an out of bounds index causes undefined
-    /// behaviour.
-    unsafe fn count_at(i: u32) -> u32;
-}
+/// Implemented on coproducts where variant I has type X. Allows replacing
+/// that variant with a Y, keeping the others as they are.
+pub trait MapVariant<I, X, Y>: Sized {
+    type Mapped;
+
+    fn try_map_variant<E, F>(self, f: F) -> Result<Self::Mapped, E>
+    where
+        F: FnOnce(X) -> Result<Y, E>;
 
-impl IndexList for EmptyUnion {
-    #[inline]
-    unsafe fn count_at(_: u32) -> u32 {
-        unreachable_unchecked()
+    fn map_variant<F>(self, f: F) -> Self::Mapped
+    where
+        F: FnOnce(X) -> Y,
+    {
+        match self.try_map_variant(|x| Ok::<_, core::convert::Infallible>(f(x))) {
+            Ok(mapped) => mapped,
+            Err(e) => match e {},
+        }
     }
 }
 
-impl<H: Count, T: IndexList> IndexList for Union<H, T> {
-    unsafe fn count_at(i: u32) -> u32 {
-        if i == 0 {
-            H::count()
-        } else {
-            T::count_at(i - 1)
-        }
+impl<I, X, Y, U, Tag: Discriminant> MapVariant<I, X, Y> for LeakingCoproduct<U, Tag>
+where
+    U: UnionAt<I, X> + Substitute<I, Y>,
+    I: Count,
+{
+    type Mapped = LeakingCoproduct<U::Substituted, Tag>;
+
+    fn try_map_variant<E, F>(self, f: F) -> Result<Self::Mapped, E>
+    where
+        F: FnOnce(X) -> Result<Y, E>,
+    {
+        Ok(LeakingCoproduct {
+            tag: self.tag,
+            union: if self.tag == Tag::of::<I>() {
+                UnionAt::inject(f(unsafe { self.union.take() })?)
+            } else {
+                // Every variant except the Ith is the same in both unions.
+                unsafe { union_transmute(self.union) }
+            },
+        })
     }
 }
 
+/// Implemented on lists of indices, providing them as a table of tags.
+trait TagTable<Tag> {
+    const LEN: u32;
+    type Table: Copy;
+    const TABLE: Self::Table;
+}
+
+impl<Tag> TagTable<Tag> for EmptyUnion {
+    const LEN: u32 = 0;
+    type Table = ();
+    const TABLE: () = ();
+}
+
+impl<H: TagFor<Tag>, T: TagTable<Tag>, Tag: Copy> TagTable<Tag> for Union<H, T> {
+    const LEN: u32 = T::LEN + 1;
+    type Table = Row<Tag, T::Table>;
+    const TABLE: Self::Table = Row(H::TAG, T::TABLE);
+}
+
 /// Implemented on Coproducts that Source can be embedded into.
 pub trait Embed<Target, Indices> {
     fn embed(self) -> Target;
 }
 
-impl<Res> Embed<Res, EmptyUnion> for LeakingCoproduct<EmptyUnion> {
-    fn embed(self) -> Res {
-        match self.union {}
-    }
-}
+/// Implemented on Unions whose variants are found in Res at Indices.
+trait EmbedUnion<Res, Indices> {}
 
-impl<Res, IH, IT, H, T> Embed<LeakingCoproduct<Res>, Union<IH, IT>>
-    for LeakingCoproduct<Union<H, T>>
+impl<Res> EmbedUnion<Res, EmptyUnion> for EmptyUnion {}
+
+impl<Res, IH, IT, H, T> EmbedUnion<Res, Union<IH, IT>> for Union<H, T>
 where
     Res: UnionAt<IH, H>,
-    Union<IH, IT>: IndexList,
-    LeakingCoproduct<T>: Embed<LeakingCoproduct<Res>, IT>,
+    T: EmbedUnion<Res, IT>,
 {
-    fn embed(self) -> LeakingCoproduct<Res> {
+}
+
+impl<U, Res, Indices, Tag, Target> Embed<LeakingCoproduct<Res, Target>, Indices>
+    for LeakingCoproduct<U, Tag>
+where
+    U: EmbedUnion<Res, Indices>,
+    Indices: TagTable<Target>,
+    Tag: Discriminant,
+    Target: Discriminant,
+{
+    fn embed(self) -> LeakingCoproduct<Res, Target> {
         LeakingCoproduct {
-            tag: unsafe { Union::count_at(self.tag) },
+            tag: unsafe { lookup(Indices::TABLE, self.tag.index()) },
             union: unsafe { union_transmute(self.union) },
         }
     }
@@ -127,43 +209,118 @@ pub trait Split<Selection, Indices> {
     fn split(self) -> Result<Selection, Self::Remainder>;
 }
 
-impl<ToSplit, THead, TTail, NHead: Count, NTail, Rem>
-    Split<LeakingCoproduct<Union<THead, TTail>>, Union<NHead, NTail>> for ToSplit
+/// Implemented on Unions that contain Selection. Each of the Indices is
+/// relative to the Union that remains after removing the previous ones.
+trait Remove<Selection, Indices> {
+    type Remainder;
+}
+
+impl<U> Remove<EmptyUnion, EmptyUnion> for U {
+    type Remainder = U;
+}
+
+impl<U, THead, TTail, NHead, NTail> Remove<Union<THead, TTail>, Union<NHead, NTail>> for U
 where
-    ToSplit: At<NHead, THead, Pruned = Rem>,
-    Rem: Split<LeakingCoproduct<TTail>, NTail>,
+    U: UnionAt<NHead, THead>,
+    U::Pruned: Remove<TTail, NTail>,
 {
-    type Remainder = Rem::Remainder;
+    type Remainder = <U::Pruned as Remove<TTail, NTail>>::Remainder;
+}
+
+/// Where a variant ends up when splitting a coproduct.
+#[derive(Clone, Copy)]
+struct Route {
+    selected: bool,
+    index: u32,
+}
 
-    fn split(self) -> Result<LeakingCoproduct<Union<THead, TTail>>, Self::Remainder> {
-        match self.uninject() {
-            Ok(found) => Ok(LeakingCoproduct::inject(found)),
-            Err(rest) => rest.split().map(|subset| LeakingCoproduct {
-                tag: subset.tag + 1,
-                union: Union {
-                    tail: ManuallyDrop::new(subset.union),
-                },
-            }),
+/// Computes the [Route] of variant `i` by removing the Indices one by one.
+const fn route<Indices: TagTable<u32>>(i: u32) -> Route {
+    let table = Indices::TABLE;
+    let indices = &table as *const Indices::Table as *const u32;
+    let mut index = i;
+    let mut k = 0;
+    while k < Indices::LEN {
+        let removed = unsafe { *indices.add(k as usize) };
+        if index == removed {
+            return Route {
+                selected: true,
+                index: k,
+            };
+        }
+        if index > removed {
+            index -= 1;
         }
+        k += 1;
     }
+    Route {
+        selected: false,
+        index,
+    }
+}
+
+/// Implemented on the Union remaining after the first Pos variants,
+/// providing the [Route] of each of its variants.
+trait RouteTable<Pos, Indices> {
+    type Table: Copy;
+    const TABLE: Self::Table;
 }
 
-impl<ToSplit> Split<LeakingCoproduct<EmptyUnion>, EmptyUnion> for ToSplit {
-    type Remainder = Self;
+impl<Pos, Indices> RouteTable<Pos, Indices> for EmptyUnion {
+    type Table = ();
+    const TABLE: () = ();
+}
+
+impl<Pos: Count, Indices: TagTable<u32>, H, T> RouteTable<Pos, Indices> for Union<H, T>
+where
+    T: RouteTable<There<Pos>, Indices>,
+{
+    type Table = Row<Route, T::Table>;
+    const TABLE: Self::Table = Row(route::<Indices>(Pos::INDEX), T::TABLE);
+}
 
-    #[inline(always)]
-    fn split(self) -> Result<LeakingCoproduct<EmptyUnion>, Self::Remainder> {
-        Err(self)
+impl<U, Selection, Indices, Tag, Target> Split<LeakingCoproduct<Selection, Target>, Indices>
+    for LeakingCoproduct<U, Tag>
+where
+    U: Remove<Selection, Indices> + RouteTable<Here, Indices>,
+    Indices: TagTable<u32>,
+    Tag: Discriminant,
+    Target: Discriminant,
+{
+    type Remainder = LeakingCoproduct<U::Remainder, Tag>;
+
+    fn split(self) -> Result<LeakingCoproduct<Selection, Target>, Self::Remainder> {
+        const {
+            assert!(
+                Indices::LEN == 0 || Indices::LEN - 1 <= Target::MAX_INDEX,
+                "variant index does not fit in the tag type"
+            )
+        };
+        let route: Route = unsafe { lookup(U::TABLE, self.tag.index()) };
+        // Every variant starts at the beginning of the unions, and the
+        // remaining ones have indices no bigger than in the original.
+        if route.selected {
+            Ok(LeakingCoproduct {
+                tag: unsafe { Target::from_index_unchecked(route.index) },
+                union: unsafe { union_transmute(self.union) },
+            })
+        } else {
+            Err(LeakingCoproduct {
+                tag: unsafe { Tag::from_index_unchecked(route.index) },
+                union: unsafe { union_transmute(self.union) },
+            })
+        }
     }
 }
 
-impl<H, T> LeakingCoproduct<Union<H, T>> {
-    fn take_head(self) -> Result<H, LeakingCoproduct<T>> {
-        if self.tag == 0 {
+impl<H, T, Tag: Discriminant> LeakingCoproduct<Union<H, T>, Tag> {
+    fn take_head(self) -> Result<H, LeakingCoproduct<T, Tag>> {
+        if self.tag.index() == 0 {
             Ok(ManuallyDrop::into_inner(unsafe { self.union.head }))
         } else {
             Err(LeakingCoproduct {
-                tag: self.tag - 1,
+                // Smaller than the current tag, so it fits.
+                tag: unsafe { Tag::from_index_unchecked(self.tag.index() - 1) },
                 union: ManuallyDrop::into_inner(unsafe { self.union.tail }),
             })
         }
@@ -172,16 +329,16 @@ impl<H, T> LeakingCoproduct<Union<H, T>> {
 
 /// Unwrapping is a bit more difficult for Coproduct than for CopyableCoproduct,
 /// so unwrap needs to be statically dispatched.
-trait CoproductWrapper<T> {
+trait CoproductWrapper<T, Tag> {
     // Returning a LeakingCoproduct doesn't cause leaks as it is private,
     // which guarantees that library users won't get their hands on it.
     // It will either be wrapped again or destroyed by the take method.
-    fn unwrap(self) -> LeakingCoproduct<T>;
+    fn unwrap(self) -> LeakingCoproduct<T, Tag>;
 }
 
 macro_rules! define_methods {
     ($type: ident, $trait: ident) => {
-        impl<I, X, U: $trait> At<I, X> for $type<U>
+        impl<I, X, U: $trait, Tag: Discriminant> At<I, X> for $type<U, Tag>
         where
             U: UnionAt<I, X>,
             U::Pruned: $trait,
@@ -195,10 +352,26 @@ macro_rules! define_methods {
                 self.unwrap().uninject().map_err($type)
             }
 
-            type Pruned = $type<U::Pruned>;
+            type Pruned = $type<U::Pruned, Tag>;
         }
 
-        impl<T: $trait> $type<T> {
+        impl<I, X, Y, U: $trait, Tag: Discriminant> MapVariant<I, X, Y> for $type<U, Tag>
+        where
+            U: UnionAt<I, X> + Substitute<I, Y>,
+            U::Substituted: $trait,
+            I: Count,
+        {
+            type Mapped = $type<U::Substituted, Tag>;
+
+            fn try_map_variant<E, F>(self, f: F) -> Result<Self::Mapped, E>
+            where
+                F: FnOnce(X) -> Result<Y, E>,
+            {
+                self.unwrap().try_map_variant(f).map($type)
+            }
+        }
+
+        impl<T: $trait, Tag: Discriminant> $type<T, Tag> {
             /// Create a new coproduct that holds the given value.
             pub fn inject<I, X>(x: X) -> Self
             where
@@ -239,6 +412,202 @@ macro_rules! define_methods {
                 <Self as At<I, X>>::uninject(self)
             }
 
+            /// Returns true if the coproduct contains an X.
+            pub fn is<I, X>(&self) -> bool
+            where
+                T: UnionAt<I, X>,
+                I: Count,
+            {
+                self.0.tag == Tag::of::<I>()
+            }
+
+            /// Returns a reference to the contained value if it is an X.
+            /// ```
+            /// # use coproduct::CopyableCoproduct;
+            /// let c: CopyableCoproduct!(u8, char) = CopyableCoproduct::inject('x');
+            /// assert_eq!(c.get::<_, char>(), Some(&'x'));
+            /// assert_eq!(c.get::<_, u8>(), None);
+            /// ```
+            pub fn get<I, X>(&self) -> Option<&X>
+            where
+                T: UnionAt<I, X>,
+                I: Count,
+            {
+                if self.is::<I, X>() {
+                    // All variants of a repr(C) union start at its beginning.
+                    Some(unsafe { &*(&self.0.union as *const T as *const X) })
+                } else {
+                    None
+                }
+            }
+
+            /// Returns a mutable reference to the contained value if it is an X.
+            pub fn get_mut<I, X>(&mut self) -> Option<&mut X>
+            where
+                T: UnionAt<I, X>,
+                I: Count,
+            {
+                if self.is::<I, X>() {
+                    Some(unsafe { &mut *(&mut self.0.union as *mut T as *mut X) })
+                } else {
+                    None
+                }
+            }
+
+            /// Store an X in the coproduct, returning the previous contents.
+            pub fn replace<I, X>(&mut self, x: X) -> Self
+            where
+                Self: At<I, X>,
+            {
+                core::mem::replace(self, <Self as At<I, X>>::inject(x))
+            }
+
+            /// Borrow the contents of the coproduct. The result holds a
+            /// reference to the active variant, so all the other methods can
+            /// be used without giving up the original.
+            /// ```
+            /// # use coproduct::Coproduct;
+            /// let c: Coproduct!(u8, String) = Coproduct::inject(String::from("hi"));
+            /// assert_eq!(c.as_ref().uninject::<_, &String>().map(|s| s.len()), Ok(2));
+            /// assert!(c.as_ref().uninject::<_, &u8>().is_err());
+            /// ```
+            pub fn as_ref<'a>(&'a self) -> $type<<T as IndexedRef<'a>>::Ref, Tag>
+            where
+                T: IndexedRef<'a>,
+            {
+                $type(LeakingCoproduct {
+                    tag: self.0.tag,
+                    union: unsafe { self.0.union.iref(self.0.tag.index()) },
+                })
+            }
+
+            /// Mutably borrow the contents of the coproduct.
+            ///
+            /// Always returns a [Coproduct] because mutable references
+            /// are not [Copy].
+            pub fn as_mut<'a>(&'a mut self) -> Coproduct<<T as IndexedRef<'a>>::Mut, Tag>
+            where
+                T: IndexedRef<'a>,
+            {
+                Coproduct(LeakingCoproduct {
+                    tag: self.0.tag,
+                    union: unsafe { self.0.union.imut(self.0.tag.index()) },
+                })
+            }
+
+            /// Transform the X variant into a Y. The Y takes the place of
+            /// the X in the resulting coproduct; the other variants are unchanged.
+            /// ```
+            /// # use coproduct::Coproduct;
+            /// let c: Coproduct!(String, u8) = Coproduct::inject(8u8);
+            /// let c: Coproduct!(String, u32) = c.map_variant(|x: u8| x as u32 * 1000);
+            /// assert_eq!(c.uninject(), Ok(8000u32));
+            /// ```
+            pub fn map_variant<I, X, Y, F>(self, f: F) -> <Self as MapVariant<I, X, Y>>::Mapped
+            where
+                Self: MapVariant<I, X, Y>,
+                F: FnOnce(X) -> Y,
+            {
+                <Self as MapVariant<I, X, Y>>::map_variant(self, f)
+            }
+
+            /// Like [map_variant](Self::map_variant) but the transformation can fail.
+            pub fn try_map_variant<I, X, Y, E, F>(
+                self,
+                f: F,
+            ) -> Result<<Self as MapVariant<I, X, Y>>::Mapped, E>
+            where
+                Self: MapVariant<I, X, Y>,
+                F: FnOnce(X) -> Result<Y, E>,
+            {
+                <Self as MapVariant<I, X, Y>>::try_map_variant(self, f)
+            }
+
+            /// Apply a function to whichever variant the coproduct contains.
+            /// See [Poly].
+            ///
+            /// The result is the same kind of coproduct as the input, so
+            /// mapping a [CopyableCoproduct] requires the outputs to be [Copy].
+            pub fn map_all<F>(self, mut f: F) -> $type<T::Output, Tag>
+            where
+                T: IndexedMap<F>,
+                T::Output: $trait,
+            {
+                let c = self.unwrap();
+                $type(LeakingCoproduct {
+                    tag: c.tag,
+                    union: unsafe { c.union.imap(&mut f, c.tag.index()) },
+                })
+            }
+
+            /// Consume the coproduct with one function per variant, given
+            /// as a heterogeneous list in the same order as the variants.
+            /// ```
+            /// # use coproduct::{hlist, CopyableCoproduct};
+            /// let c: CopyableCoproduct!(u8, bool) = CopyableCoproduct::inject(true);
+            /// let n = c.fold(hlist![|x: u8| x as u32, |b: bool| b as u32 + 10]);
+            /// assert_eq!(n, 11);
+            /// ```
+            pub fn fold<Fs, R>(self, fs: Fs) -> R
+            where
+                T: IndexedFold<Fs, R>,
+            {
+                let c = self.unwrap();
+                unsafe { c.union.ifold(fs, c.tag.index()) }
+            }
+
+            /// Convert the contents into R, which every variant must be
+            /// convertible to.
+            /// ```
+            /// # use coproduct::CopyableCoproduct;
+            /// let c: CopyableCoproduct!(u8, i32, f32) = CopyableCoproduct::inject(-2i32);
+            /// assert_eq!(c.unify::<f64>(), -2.0);
+            /// ```
+            pub fn unify<R>(self) -> R
+            where
+                T: IndexedInto<R>,
+            {
+                let c = self.unwrap();
+                unsafe { c.union.iinto(c.tag.index()) }
+            }
+
+            /// View the contents as a trait object. See [DynFrom].
+            pub fn as_dyn<D: ?Sized>(&self) -> &D
+            where
+                T: IndexedDyn<D>,
+            {
+                unsafe { self.0.union.idyn(self.0.tag.index()) }
+            }
+
+            /// View the contents as a mutable trait object. See [DynFrom].
+            pub fn as_dyn_mut<D: ?Sized>(&mut self) -> &mut D
+            where
+                T: IndexedDyn<D>,
+            {
+                unsafe { self.0.union.idyn_mut(self.0.tag.index()) }
+            }
+
+            /// Move the contents into a boxed trait object. See [DynFromBox].
+            /// ```
+            /// # use coproduct::Coproduct;
+            /// let c: Coproduct!(u8, String) = Coproduct::inject(5u8);
+            /// let boxed: Box<dyn std::fmt::Display> = c.into_boxed_dyn();
+            /// assert_eq!(boxed.to_string(), "5");
+            /// ```
+            #[cfg(feature = "alloc")]
+            pub fn into_boxed_dyn<D: ?Sized>(self) -> alloc::boxed::Box<D>
+            where
+                T: crate::IndexedDynBox<D>,
+            {
+                let c = self.unwrap();
+                unsafe { c.union.ibox(c.tag.index()) }
+            }
+
+            /// Start an exhaustive case analysis. See [Matcher].
+            pub fn matcher<R>(self) -> Matcher<Self, R> {
+                Matcher::new(self)
+            }
+
             /// Convert a coproduct into another with more variants.
             pub fn embed<U, I>(self) -> U
             where
@@ -256,19 +625,19 @@ macro_rules! define_methods {
             }
         }
 
-        impl<H, T> $type<Union<H, T>>
+        impl<H, T, Tag: Discriminant> $type<Union<H, T>, Tag>
         where
             Union<H, T>: $trait,
             T: $trait,
         {
             /// Try to take the first variant out. On failure, return the
             /// remaining variants.
-            pub fn take_head(self) -> Result<H, $type<T>> {
+            pub fn take_head(self) -> Result<H, $type<T, Tag>> {
                 self.unwrap().take_head().map_err($type)
             }
         }
 
-        impl $type<EmptyUnion> {
+        impl<Tag: Discriminant> $type<EmptyUnion, Tag> {
             /// From falsehood, anything follows.
             ///
             /// Given a coproduct that cannot contain anything,
@@ -278,37 +647,52 @@ macro_rules! define_methods {
             }
         }
 
-        impl<T: $trait, I, U: $trait> Embed<$type<T>, I> for $type<U>
+        impl<T: $trait, I, U: $trait, Tag: Discriminant, Target: Discriminant>
+            Embed<$type<T, Target>, I> for $type<U, Tag>
         where
-            LeakingCoproduct<U>: Embed<LeakingCoproduct<T>, I>,
+            LeakingCoproduct<U, Tag>: Embed<LeakingCoproduct<T, Target>, I>,
         {
-            fn embed(self) -> $type<T> {
+            fn embed(self) -> $type<T, Target> {
                 $type(self.unwrap().embed())
             }
         }
 
-        impl<T: $trait, I, U: $trait, Rem> Split<$type<T>, I> for $type<U>
+        impl<T: $trait, I, U: $trait, Rem, Tag: Discriminant, Target: Discriminant>
+            Split<$type<T, Target>, I> for $type<U, Tag>
         where
-            LeakingCoproduct<U>: Split<LeakingCoproduct<T>, I, Remainder = LeakingCoproduct<Rem>>,
+            LeakingCoproduct<U, Tag>:
+                Split<LeakingCoproduct<T, Target>, I, Remainder = LeakingCoproduct<Rem, Tag>>,
             Rem: $trait,
         {
-            type Remainder = $type<Rem>;
+            type Remainder = $type<Rem, Tag>;
 
-            fn split(self) -> Result<$type<T>, $type<Rem>> {
+            fn split(self) -> Result<$type<T, Target>, $type<Rem, Tag>> {
                 self.unwrap().split().map($type).map_err($type)
             }
         }
 
         #[cfg(feature = "type_inequality_hack")]
-        impl<T: $trait, U: $trait, Ds> Merge<$type<T>, Ds> for $type<U>
+        impl<T: $trait, U: $trait, Ds, Tag: Discriminant> Merge<$type<T, Tag>, Ds> for $type<U, Tag>
         where
             U: Merge<T, Ds>,
             U::Merged: $trait,
         {
-            type Merged = $type<U::Merged>;
+            type Merged = $type<U::Merged, Tag>;
         }
 
-        impl<T> PartialEq for $type<T>
+        impl<'a, T: $trait, Tag: Discriminant> From<&'a $type<T, Tag>> for Active<&'a T> {
+            fn from(c: &'a $type<T, Tag>) -> Self {
+                unsafe { Active::new(&c.0.union, c.0.tag.index()) }
+            }
+        }
+
+        impl<'a, T: $trait, Tag: Discriminant> From<&'a mut $type<T, Tag>> for Active<&'a mut T> {
+            fn from(c: &'a mut $type<T, Tag>) -> Self {
+                unsafe { Active::new(&mut c.0.union, c.0.tag.index()) }
+            }
+        }
+
+        impl<T, Tag: Discriminant> PartialEq for $type<T, Tag>
         where
             T: IndexedEq + $trait,
         {
@@ -317,8 +701,42 @@ macro_rules! define_methods {
             }
         }
 
-        impl<T: IndexedDebug + $trait> core::fmt::Debug for $type<T> {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        /// Displays the active variant.
+        impl<T: IndexedDisplay + $trait, Tag: Discriminant> core::fmt::Display for $type<T, Tag> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                unsafe { self.0.union.idisplay(f, self.0.tag.index()) }
+            }
+        }
+
+        /// The source of the error is the source of the active variant.
+        impl<T: IndexedError + $trait, Tag: Discriminant> core::error::Error for $type<T, Tag> {
+            fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+                unsafe { self.0.union.isource(self.0.tag.index()) }
+            }
+        }
+
+        impl<T: IndexedTotalEq + $trait, Tag: Discriminant> Eq for $type<T, Tag> {}
+
+        impl<T: IndexedHash + $trait, Tag: Discriminant> core::hash::Hash for $type<T, Tag> {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state)
+            }
+        }
+
+        impl<T: IndexedPartialOrd + $trait, Tag: Discriminant> PartialOrd for $type<T, Tag> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.0.partial_cmp(&other.0)
+            }
+        }
+
+        impl<T: IndexedOrd + $trait, Tag: Discriminant> Ord for $type<T, Tag> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        impl<T: IndexedDebug + $trait, Tag: Discriminant> core::fmt::Debug for $type<T, Tag> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 f.debug_tuple(stringify!($type)).field(&self.0).finish()
             }
         }
@@ -326,55 +744,253 @@ macro_rules! define_methods {
 }
 
 /// A coproduct that can only hold copyable types.
+///
+/// The active variant is stored in a `Tag`. See [Discriminant].
+///
+/// The tag is stored next to the Union, never inside a niche or the padding
+/// of a variant. With a one-byte tag, which the macros pick for up to 255
+/// variants, a coproduct is therefore bigger than an enum with the same
+/// variants by at most the largest alignment of any of the variants, which
+/// need not be the alignment of the largest one. Wider tags take up more
+/// space. [PackedCoproduct](struct@PackedCoproduct) avoids the
+/// padding if there are many coproducts to store.
+/// ```
+/// # use coproduct::CopyableCoproduct;
+/// use core::mem::size_of;
+///
+/// #[allow(dead_code)]
+/// enum Enum {
+///     A(u32),
+///     B(bool),
+/// }
+///
+/// assert_eq!(size_of::<Enum>(), 8);
+/// assert_eq!(size_of::<CopyableCoproduct!(u32, bool)>(), 8);
+/// assert_eq!(size_of::<Option<Enum>>(), 8);
+/// assert_eq!(size_of::<Option<CopyableCoproduct!(u32, bool)>>(), 8);
+///
+/// #[allow(dead_code)]
+/// enum Large {
+///     A([u8; 100]),
+///     B(u64),
+/// }
+///
+/// // 8 bytes more than the enum, the alignment of u64.
+/// assert_eq!(size_of::<Large>(), 104);
+/// assert_eq!(size_of::<CopyableCoproduct!([u8; 100], u64)>(), 112);
+/// ```
 #[derive(Copy, Clone)]
-pub struct CopyableCoproduct<T>(LeakingCoproduct<T>)
+pub struct CopyableCoproduct<T, Tag = NonZeroU8>(LeakingCoproduct<T, Tag>)
 where
-    T: Copy;
+    T: Copy,
+    Tag: Discriminant;
 
-impl<T: Copy> Clone for LeakingCoproduct<T> {
+impl<T: Copy, Tag: Copy> Clone for LeakingCoproduct<T, Tag> {
     fn clone(&self) -> Self {
         *self
     }
 }
-impl<T: Copy> Copy for LeakingCoproduct<T> {}
+impl<T: Copy, Tag: Copy> Copy for LeakingCoproduct<T, Tag> {}
 
-impl<T: Copy> CoproductWrapper<T> for CopyableCoproduct<T> {
-    fn unwrap(self) -> LeakingCoproduct<T> {
+impl<T: Copy, Tag: Discriminant> CoproductWrapper<T, Tag> for CopyableCoproduct<T, Tag> {
+    fn unwrap(self) -> LeakingCoproduct<T, Tag> {
         self.0
     }
 }
 
 define_methods!(CopyableCoproduct, Copy);
 
+impl<T: Copy, Tag: Discriminant> CopyableCoproduct<T, Tag> {
+    /// Like [inject](Self::inject) but usable in constants and statics.
+    /// ```
+    /// # use coproduct::CopyableCoproduct;
+    /// #[derive(Clone, Copy, Debug, PartialEq)]
+    /// struct Add(u8);
+    /// #[derive(Clone, Copy, Debug, PartialEq)]
+    /// struct Nop;
+    ///
+    /// static OPS: [CopyableCoproduct!(Add, Nop); 2] = [
+    ///     CopyableCoproduct::new_const(Add(1)),
+    ///     CopyableCoproduct::new_const(Nop),
+    /// ];
+    /// assert_eq!(OPS[1].uninject(), Ok(Nop));
+    /// ```
+    pub const fn new_const<I, X>(x: X) -> Self
+    where
+        T: UnionAt<I, X>,
+        I: TagFor<Tag>,
+    {
+        CopyableCoproduct(LeakingCoproduct {
+            tag: I::TAG,
+            // All variants of a repr(C) union start at its beginning.
+            union: unsafe { union_transmute(x) },
+        })
+    }
+}
+
 /// Builds a [CopyableCoproduct] that can hold the types given as arguments.
 #[macro_export]
 macro_rules! CopyableCoproduct {
     ( $( $t:ty ),+ ) => (
-        $crate::CopyableCoproduct<$crate::MkUnion!( $( $t ),+ )>
+        $crate::CopyableCoproduct<
+            $crate::MkUnion!( $( $t ),+ ),
+            $crate::__smallest_tag!( $( $t ),+ ),
+        >
+    );
+}
+
+/// A [CopyableCoproduct] without padding, for storing many of them compactly.
+///
+/// A CopyableCoproduct is as big as its Union and its tag together,
+/// rounded up to the alignment of the largest variant. The Union itself is
+/// also rounded up, so a coproduct of a `u64` and a `[u8; 9]` takes 24 bytes,
+/// more than the 16 bytes of an enum with the same variants.
+///
+/// A PackedCoproduct is exactly as big as its largest variant plus its tag,
+/// because it has an alignment of 1. An enum with the same variants can only
+/// be smaller if it manages to put its tag into a niche or the padding of
+/// one of the variants. The price is that the contents can't be borrowed,
+/// so it has to be unpacked to do anything but copy it around. For the same
+/// reason there is no packed counterpart of [Coproduct](struct@Coproduct):
+/// dropping and cloning its contents requires borrowing them.
+/// ```
+/// # use coproduct::{CopyableCoproduct, PackedCoproduct};
+/// use core::mem::size_of;
+///
+/// #[allow(dead_code)]
+/// enum Enum {
+///     A(u64),
+///     B([u8; 9]),
+/// }
+///
+/// assert_eq!(size_of::<Enum>(), 16);
+/// assert_eq!(size_of::<CopyableCoproduct!(u64, [u8; 9])>(), 24);
+/// assert_eq!(size_of::<PackedCoproduct!(u64, [u8; 9])>(), 10);
+///
+/// let c: CopyableCoproduct!(u64, [u8; 9]) = CopyableCoproduct::inject(7u64);
+/// let table: [PackedCoproduct!(u64, [u8; 9]); 4] = [c.pack(); 4];
+/// assert_eq!(table[2].unpack().uninject(), Ok(7u64));
+/// ```
+#[derive(Copy, Clone)]
+#[repr(C, packed)]
+pub struct PackedCoproduct<T, Tag = NonZeroU8>
+where
+    T: Pack,
+    Tag: Discriminant,
+{
+    union: T::Packed,
+    tag: Tag,
+}
+
+impl<T: Pack, Tag: Discriminant> CopyableCoproduct<T, Tag> {
+    /// Remove the padding. See [PackedCoproduct].
+    pub const fn pack(self) -> PackedCoproduct<T, Tag> {
+        PackedCoproduct {
+            // Variants start at the beginning of both unions.
+            union: unsafe { union_transmute(self.0.union) },
+            tag: self.0.tag,
+        }
+    }
+}
+
+impl<T: Pack, Tag: Discriminant> PackedCoproduct<T, Tag> {
+    /// Create a new coproduct that holds the given value.
+    pub fn inject<I, X>(x: X) -> Self
+    where
+        CopyableCoproduct<T, Tag>: At<I, X>,
+    {
+        CopyableCoproduct::inject(x).pack()
+    }
+
+    /// Restore the alignment, so the contents can be accessed.
+    pub const fn unpack(self) -> CopyableCoproduct<T, Tag> {
+        CopyableCoproduct(LeakingCoproduct {
+            tag: self.tag,
+            union: unsafe { union_transmute(self.union) },
+        })
+    }
+
+    /// Returns true if the coproduct contains an X.
+    pub fn is<I, X>(&self) -> bool
+    where
+        T: UnionAt<I, X>,
+        I: Count,
+    {
+        // Copy the tag as it may be unaligned.
+        let tag = self.tag;
+        tag == Tag::of::<I>()
+    }
+}
+
+impl<T: Pack, Tag: Discriminant> From<CopyableCoproduct<T, Tag>> for PackedCoproduct<T, Tag> {
+    fn from(c: CopyableCoproduct<T, Tag>) -> Self {
+        c.pack()
+    }
+}
+
+impl<T: Pack, Tag: Discriminant> From<PackedCoproduct<T, Tag>> for CopyableCoproduct<T, Tag> {
+    fn from(c: PackedCoproduct<T, Tag>) -> Self {
+        c.unpack()
+    }
+}
+
+impl<T: Pack + IndexedEq, Tag: Discriminant> PartialEq for PackedCoproduct<T, Tag> {
+    fn eq(&self, other: &Self) -> bool {
+        self.unpack() == other.unpack()
+    }
+}
+
+impl<T: Pack + IndexedTotalEq, Tag: Discriminant> Eq for PackedCoproduct<T, Tag> {}
+
+impl<T: Pack + IndexedHash, Tag: Discriminant> core::hash::Hash for PackedCoproduct<T, Tag> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.unpack().hash(state)
+    }
+}
+
+impl<T: Pack + IndexedDebug, Tag: Discriminant> core::fmt::Debug for PackedCoproduct<T, Tag> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("PackedCoproduct")
+            .field(&self.unpack().0)
+            .finish()
+    }
+}
+
+/// Builds a [PackedCoproduct] that can hold the types given as arguments.
+#[macro_export]
+macro_rules! PackedCoproduct {
+    ( $( $t:ty ),+ ) => (
+        $crate::PackedCoproduct<
+            $crate::MkUnion!( $( $t ),+ ),
+            $crate::__smallest_tag!( $( $t ),+ ),
+        >
     );
 }
 
 /// Can hold any type. You should use [CopyableCoproduct]
 /// if your types are copyable.
-pub struct Coproduct<T: IndexedDrop>(LeakingCoproduct<T>);
+///
+/// The active variant is stored in a `Tag`. See [Discriminant]. The size
+/// is bounded the same way as that of a [CopyableCoproduct].
+pub struct Coproduct<T: IndexedDrop, Tag: Discriminant = NonZeroU8>(LeakingCoproduct<T, Tag>);
 
-impl<T: IndexedClone + IndexedDrop> Clone for Coproduct<T> {
+impl<T: IndexedClone + IndexedDrop, Tag: Discriminant> Clone for Coproduct<T, Tag> {
     fn clone(&self) -> Self {
         Self(LeakingCoproduct {
             tag: self.0.tag,
-            union: unsafe { self.0.union.iclone(self.0.tag) },
+            union: unsafe { self.0.union.iclone(self.0.tag.index()) },
         })
     }
 }
 
-impl<T: IndexedDrop> Drop for Coproduct<T> {
+impl<T: IndexedDrop, Tag: Discriminant> Drop for Coproduct<T, Tag> {
     fn drop(&mut self) {
-        unsafe { self.0.union.idrop(self.0.tag) }
+        unsafe { self.0.union.idrop(self.0.tag.index()) }
     }
 }
 
-impl<T: IndexedDrop> CoproductWrapper<T> for Coproduct<T> {
-    fn unwrap(self) -> LeakingCoproduct<T> {
+impl<T: IndexedDrop, Tag: Discriminant> CoproductWrapper<T, Tag> for Coproduct<T, Tag> {
+    fn unwrap(self) -> LeakingCoproduct<T, Tag> {
         // As Coproduct is Drop, moving out of it isn't possible,
         // which necessitates ptr::read.
         // self needs to be wrapped in ManuallyDrop because otherwise it would
@@ -396,11 +1012,47 @@ where
     C::inject(x)
 }
 
+/// Implemented on values that can be converted into the coproduct C: either
+/// a variant of C or a coproduct that can be embedded into C.
+///
+/// `From` cannot be implemented for this purpose, as it would overlap with
+/// `impl<T> From<T> for T`.
+pub trait IntoCoproduct<C, Index> {
+    fn into_coproduct(self) -> C;
+}
+
+/// Index for [IntoCoproduct] when the value is a variant of the coproduct.
+pub struct Injected<I>(I);
+
+/// Index for [IntoCoproduct] when the value is a smaller coproduct.
+pub struct Embedded<I>(I);
+
+impl<X, C, I> IntoCoproduct<C, Injected<I>> for X
+where
+    C: At<I, X>,
+{
+    fn into_coproduct(self) -> C {
+        C::inject(self)
+    }
+}
+
+impl<S, C, I> IntoCoproduct<C, Embedded<I>> for S
+where
+    S: Embed<C, I>,
+{
+    fn into_coproduct(self) -> C {
+        self.embed()
+    }
+}
+
 /// Builds a [Coproduct] that can hold the types given as arguments.
 #[macro_export]
 macro_rules! Coproduct {
     ( $( $t:ty ),+ ) => (
-        $crate::Coproduct<$crate::MkUnion!( $( $t ),+ )>
+        $crate::Coproduct<
+            $crate::MkUnion!( $( $t ),+ ),
+            $crate::__smallest_tag!( $( $t ),+ ),
+        >
     );
 }
 
@@ -408,6 +1060,7 @@ macro_rules! Coproduct {
 mod tests {
     use super::*;
     use crate::union::*;
+    use core::num::NonZeroU32;
 
     #[test]
     fn inject_uninject() {
@@ -421,10 +1074,333 @@ mod tests {
         let _: Coproduct!(String) = Coproduct::inject("hello".into());
     }
 
+    #[test]
+    fn borrow() {
+        let mut c: Coproduct!(u8, String) = Coproduct::inject(String::from("hello"));
+        if let Ok(s) = c.as_mut().uninject::<_, &mut String>() {
+            s.push('!');
+        }
+        assert_eq!(c.as_ref().uninject(), Ok(&String::from("hello!")));
+
+        let c: CopyableCoproduct!(u8, bool) = CopyableCoproduct::inject(true);
+        let r = c.as_ref();
+        assert!(r.uninject::<_, &u8>().is_err());
+        assert_eq!(r.uninject(), Ok(&true));
+    }
+
+    #[test]
+    fn accessors() {
+        let mut c: Coproduct!(u8, String) = Coproduct::inject(3u8);
+        assert!(c.is::<_, u8>());
+        assert!(!c.is::<_, String>());
+        *c.get_mut::<_, u8>().unwrap() += 1;
+        assert_eq!(c.get::<_, u8>(), Some(&4));
+        assert_eq!(c.get_mut::<_, String>(), None);
+
+        let old = c.replace(String::from("new"));
+        assert_eq!(old.uninject(), Ok(4u8));
+        assert_eq!(c.get::<_, String>().map(|s| s.as_str()), Some("new"));
+    }
+
+    #[test]
+    fn matcher() {
+        let describe = |c: CopyableCoproduct!(u8, bool, char)| {
+            c.matcher()
+                .on(|x: u8| x.to_string())
+                .on(|b: bool| b.to_string())
+                .on(|c: char| c.to_string())
+                .finish()
+        };
+        assert_eq!(describe(CopyableCoproduct::inject(5u8)), "5");
+        assert_eq!(describe(CopyableCoproduct::inject(false)), "false");
+        assert_eq!(describe(CopyableCoproduct::inject('c')), "c");
+    }
+
+    #[test]
+    fn match_macro() {
+        let c: CopyableCoproduct!(u8, (u16, bool), char) = CopyableCoproduct::inject((7u16, true));
+        let r = crate::match_coproduct!(c {
+            x: u8 => x as u32,
+            (x, _): (u16, bool) => x as u32,
+            _: char => 0,
+        });
+        assert_eq!(r, 7);
+
+        let r = crate::match_coproduct!(c.embed::<CopyableCoproduct!(i8, u8, (u16, bool), char), _>() {
+            _: (u16, bool) => None,
+            rest => Some(rest),
+        });
+        assert!(r.is_none());
+    }
+
+    #[test]
+    fn map_all() {
+        struct Stringify;
+        impl<X: ToString> Poly<X> for Stringify {
+            type Output = String;
+            fn call(&mut self, x: X) -> String {
+                x.to_string()
+            }
+        }
+
+        let c: Coproduct!(u8, bool) = Coproduct::inject(true);
+        let s: Coproduct!(String, String) = c.map_all(Stringify);
+        assert_eq!(
+            s.take_head().unwrap_err().uninject(),
+            Ok("true".to_string())
+        );
+    }
+
+    #[test]
+    fn map_variant() {
+        type Narrowed = CopyableCoproduct!(u8, u16, bool);
+        let c: CopyableCoproduct!(u8, i64, bool) = CopyableCoproduct::inject(-3i64);
+        let r: Result<Narrowed, _> = c.try_map_variant(|x: i64| u16::try_from(x));
+        assert!(r.is_err());
+
+        let c: CopyableCoproduct!(u8, i64, bool) = CopyableCoproduct::inject(true);
+        let r: Narrowed = c.try_map_variant(|x: i64| u16::try_from(x)).unwrap();
+        assert_eq!(r.uninject(), Ok(true));
+    }
+
+    #[test]
+    fn fold() {
+        let c: Coproduct!(u8, String) = Coproduct::inject(String::from("abc"));
+        let len = c.fold(crate::hlist![|_| 1, |s: String| s.len()]);
+        assert_eq!(len, 3);
+    }
+
+    #[test]
+    fn unify() {
+        let c: Coproduct!(&str, String, char) = Coproduct::inject('x');
+        assert_eq!(c.unify::<String>(), "x");
+    }
+
+    #[test]
+    fn as_dyn() {
+        use core::fmt::{Debug, Display};
+
+        let mut c: Coproduct!(u8, String) = Coproduct::inject(String::from("a"));
+        assert_eq!(c.as_dyn::<dyn Display>().to_string(), "a");
+        c.get_mut::<_, String>().unwrap().push('b');
+        assert_eq!(format!("{:?}", c.as_dyn_mut::<dyn Debug>()), "\"ab\"");
+        #[cfg(feature = "alloc")]
+        {
+            let boxed: Box<dyn Display> = c.into_boxed_dyn();
+            assert_eq!(boxed.to_string(), "ab");
+        }
+    }
+
+    #[test]
+    fn dyn_from_by_hand() {
+        trait Named {
+            fn name(&self) -> &'static str;
+        }
+
+        struct Cat;
+        impl Named for Cat {
+            fn name(&self) -> &'static str {
+                "cat"
+            }
+        }
+
+        // Must compile regardless of the alloc feature.
+        impl crate::DynFrom<Cat> for dyn Named {
+            fn from_ref(x: &Cat) -> &Self {
+                x
+            }
+
+            fn from_mut(x: &mut Cat) -> &mut Self {
+                x
+            }
+        }
+
+        let c: Coproduct!(Cat) = Coproduct::inject(Cat);
+        assert_eq!(c.as_dyn::<dyn Named>().name(), "cat");
+    }
+
+    #[test]
+    fn hash_and_order() {
+        use std::collections::{BTreeSet, HashSet};
+
+        type C = Coproduct!(u8, String);
+        let a: C = Coproduct::inject(200u8);
+        let b: C = Coproduct::inject(String::from("a"));
+        let c: C = Coproduct::inject(String::from("b"));
+        assert!(a < b && b < c);
+
+        let sorted: Vec<C> = BTreeSet::from([c.clone(), a.clone(), b.clone()])
+            .into_iter()
+            .collect();
+        assert_eq!(sorted, vec![a.clone(), b.clone(), c]);
+
+        let set = HashSet::from([a.clone(), b.clone(), a.clone()]);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&b));
+    }
+
+    #[test]
+    fn error() {
+        use core::error::Error;
+        use core::fmt;
+
+        #[derive(Debug)]
+        struct Wrapper(std::num::ParseIntError);
+        impl fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "wrapped")
+            }
+        }
+        impl Error for Wrapper {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        type E = Coproduct!(std::fmt::Error, Wrapper);
+        let e: E = Coproduct::inject(Wrapper("x".parse::<u8>().unwrap_err()));
+        assert_eq!(e.to_string(), "wrapped");
+        assert!(e.source().is_some());
+
+        let boxed: Box<dyn Error> = Box::new(E::inject(fmt::Error));
+        assert_eq!(boxed.to_string(), fmt::Error.to_string());
+        assert!(boxed.source().is_none());
+    }
+
+    #[test]
+    fn new_const() {
+        const C: CopyableCoproduct!(u8, u64, bool) = CopyableCoproduct::new_const(7u64);
+        assert_eq!(C, CopyableCoproduct::inject(7u64));
+        static S: CopyableCoproduct!(u8, u64) = CopyableCoproduct::new_const(3u8);
+        assert_eq!(S.uninject(), Ok(3u8));
+    }
+
     #[test]
     fn embed_split() {
         let c: Coproduct!(u8, u16) = Coproduct::inject(42u16);
         let widened: Coproduct!(u8, u16, u32, u64) = c.clone().embed();
         assert_eq!(Ok(c), widened.split())
     }
+
+    #[test]
+    fn split_out_of_order() {
+        type Source = CopyableCoproduct!(u8, u16, u32, u64, i8, i16);
+        type Selection = CopyableCoproduct!(i8, u16, u64);
+        type Remainder = CopyableCoproduct!(u8, u32, i16);
+
+        let split = |c: Source| c.split::<Selection, _>();
+        assert_eq!(split(Source::inject(1u8)), Err(Remainder::inject(1u8)));
+        assert_eq!(split(Source::inject(2u16)), Ok(Selection::inject(2u16)));
+        assert_eq!(split(Source::inject(3u32)), Err(Remainder::inject(3u32)));
+        assert_eq!(split(Source::inject(4u64)), Ok(Selection::inject(4u64)));
+        assert_eq!(split(Source::inject(5i8)), Ok(Selection::inject(5i8)));
+        assert_eq!(split(Source::inject(6i16)), Err(Remainder::inject(6i16)));
+
+        let embedded: Source = Selection::inject(5i8).embed();
+        assert_eq!(embedded, Source::inject(5i8));
+        let embedded: Source = Remainder::inject(6i16).embed();
+        assert_eq!(embedded, Source::inject(6i16));
+    }
+
+    #[test]
+    fn dispatch_table() {
+        type C = Coproduct!(u8, Vec<u8>, String, Option<String>);
+        let c = C::inject(Some(String::from("last")));
+        let d = c.clone();
+        assert_eq!(c, d);
+        assert_ne!(c, C::inject(Some(String::from("other"))));
+        assert_eq!(format!("{:?}", d), r#"Coproduct(Some("last"))"#);
+        drop(c);
+        assert_eq!(d.uninject(), Ok(Some(String::from("last"))));
+    }
+
+    #[test]
+    fn tag_width() {
+        use crate::MkUnion;
+        use core::mem::size_of;
+
+        assert_eq!(size_of::<CopyableCoproduct!(u8, bool, u16)>(), 4);
+        assert_eq!(
+            size_of::<CopyableCoproduct<MkUnion!(u8, bool, u16), u16>>(),
+            4
+        );
+        assert_eq!(
+            size_of::<CopyableCoproduct<MkUnion!(u8, bool, u16), u32>>(),
+            8
+        );
+
+        let wide: Coproduct<MkUnion!(u8, String), u32> = Coproduct::inject(String::from("x"));
+        let narrow: Coproduct!(u8, String) = wide.clone().embed();
+        assert_eq!(narrow.get::<_, String>().map(String::as_str), Some("x"));
+        assert_eq!(narrow.split(), Ok(wide));
+    }
+
+    #[test]
+    fn size_against_enum() {
+        use crate::MkUnion;
+        use core::mem::{align_of, size_of};
+
+        #[allow(dead_code)]
+        enum Enum {
+            A(u64),
+            B(String),
+            C([u8; 9]),
+        }
+        type Union = MkUnion!(u64, String, [u8; 9]);
+
+        let bound = size_of::<Enum>() + align_of::<Union>();
+        assert!(size_of::<Coproduct!(u64, String, [u8; 9])>() <= bound);
+        assert!(size_of::<Coproduct<Union, u8>>() <= bound);
+
+        #[allow(dead_code)]
+        enum Small {
+            A(u8),
+            B([u16; 3]),
+        }
+        let bound = size_of::<Small>() + align_of::<u16>();
+        assert!(size_of::<CopyableCoproduct!(u8, [u16; 3])>() <= bound);
+    }
+
+    #[test]
+    fn niche() {
+        use crate::MkUnion;
+        use core::mem::size_of;
+
+        type Small = CopyableCoproduct!(u8, bool);
+        type Big = Coproduct!(u64, String, Vec<u8>);
+        assert_eq!(size_of::<Option<Small>>(), size_of::<Small>());
+        assert_eq!(size_of::<Result<Small, ()>>(), size_of::<Small>());
+        assert_eq!(size_of::<Option<Big>>(), size_of::<Big>());
+        assert_eq!(size_of::<Result<Big, ()>>(), size_of::<Big>());
+        assert_eq!(
+            size_of::<Option<Coproduct<MkUnion!(u64, String), NonZeroU32>>>(),
+            size_of::<Coproduct<MkUnion!(u64, String), NonZeroU32>>()
+        );
+
+        let c: Option<Small> = Some(CopyableCoproduct::inject(true));
+        assert_eq!(c.and_then(|c| c.uninject::<_, bool>().ok()), Some(true));
+    }
+
+    #[test]
+    fn packed() {
+        use core::mem::size_of;
+
+        type C = CopyableCoproduct!(u32, (u16, u8), char);
+        type P = PackedCoproduct!(u32, (u16, u8), char);
+        assert_eq!(size_of::<C>(), 8);
+        assert_eq!(size_of::<P>(), 5);
+        assert_eq!(size_of::<Option<P>>(), 5);
+        assert_eq!(size_of::<[P; 10]>(), 50);
+
+        let table: [P; 3] = [
+            C::inject(7u32).pack(),
+            P::inject((1u16, 2u8)),
+            CopyableCoproduct::new_const('x').pack(),
+        ];
+        assert!(table[1].is::<_, (u16, u8)>());
+        assert_eq!(table[1].unpack().uninject(), Ok((1u16, 2u8)));
+        assert_eq!(C::from(table[2]).uninject(), Ok('x'));
+        assert_eq!(table[0], P::from(C::inject(7u32)));
+        assert_ne!(table[0], table[2]);
+    }
 }
@@ -1,22 +1,36 @@
 pub struct Here;
 pub struct There<T>(T);
 
+/// Converts a type-level index into a number.
+///
+/// The number is available as a constant, so it can be used in `const`
+/// contexts and patterns.
+/// ```
+/// # use coproduct::{Count, Here, There};
+/// const TWO: u32 = <There<There<Here>> as Count>::INDEX;
+/// match 2 {
+///     <There<Here> as Count>::INDEX => unreachable!(),
+///     TWO => {}
+///     _ => unreachable!(),
+/// }
+/// ```
 pub trait Count {
-    fn count() -> u32;
-}
+    const INDEX: u32;
 
-impl Count for Here {
-    #[inline]
+    /// Same as [Count::INDEX].
+    #[inline(always)]
     fn count() -> u32 {
-        0
+        Self::INDEX
     }
 }
 
+impl Count for Here {
+    const INDEX: u32 = 0;
+}
+
 impl<N> Count for There<N>
 where
     N: Count,
 {
-    fn count() -> u32 {
-        N::count() + 1
-    }
+    const INDEX: u32 = N::INDEX + 1;
 }
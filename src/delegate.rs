@@ -0,0 +1,118 @@
+/// Defines a trait and implements it for every coproduct whose variants all
+/// implement it. The implementation calls the method of the active variant.
+///
+/// Only methods taking `&self` or `&mut self` and arguments bound to plain
+/// identifiers are supported. The trait cannot have generic parameters,
+/// supertraits, associated items or default method bodies.
+/// ```
+/// # use coproduct::{delegate, Coproduct, CopyableCoproduct};
+/// delegate! {
+///     pub trait Shape {
+///         fn area(&self) -> f64;
+///         fn scale(&mut self, factor: f64);
+///     }
+/// }
+///
+/// #[derive(Clone, Copy)]
+/// struct Square(f64);
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+///     fn scale(&mut self, factor: f64) {
+///         self.0 *= factor;
+///     }
+/// }
+///
+/// #[derive(Clone, Copy)]
+/// struct Rectangle(f64, f64);
+/// impl Shape for Rectangle {
+///     fn area(&self) -> f64 {
+///         self.0 * self.1
+///     }
+///     fn scale(&mut self, factor: f64) {
+///         self.0 *= factor;
+///         self.1 *= factor;
+///     }
+/// }
+///
+/// let mut c: Coproduct!(Square, Rectangle) = Coproduct::inject(Rectangle(1.0, 2.0));
+/// c.scale(2.0);
+/// assert_eq!(c.area(), 8.0);
+///
+/// let c: CopyableCoproduct!(Square, Rectangle) = CopyableCoproduct::inject(Square(3.0));
+/// assert_eq!(c.area(), 9.0);
+/// ```
+#[macro_export]
+macro_rules! delegate {
+    (@parse $head:tt $trait:ident [$($done:tt)*]
+        $(#[$m:meta])* fn $name:ident(&self $(, $a:ident: $t:ty)* $(,)?) $(-> $r:ty)?;
+        $($rest:tt)*
+    ) => {
+        $crate::delegate!(@parse $head $trait
+            [$($done)* [($(#[$m])*) () $name ($($a: $t),*) ($($r)?)]]
+            $($rest)*
+        );
+    };
+    (@parse $head:tt $trait:ident [$($done:tt)*]
+        $(#[$m:meta])* fn $name:ident(&mut self $(, $a:ident: $t:ty)* $(,)?) $(-> $r:ty)?;
+        $($rest:tt)*
+    ) => {
+        $crate::delegate!(@parse $head $trait
+            [$($done)* [($(#[$m])*) (mut) $name ($($a: $t),*) ($($r)?)]]
+            $($rest)*
+        );
+    };
+    (@parse ($($head:tt)*) $trait:ident
+        [$([($($m:tt)*) ($($mut:tt)?) $name:ident ($($a:ident: $t:ty),*) ($($r:ty)?)])*]
+    ) => {
+        $($head)* trait $trait {
+            $($($m)* fn $name(& $($mut)? self $(, $a: $t)*) $(-> $r)?;)*
+        }
+
+        const _: () = {
+            pub trait __Delegate {
+                $(fn $name(active: $crate::Active<& $($mut)? Self> $(, $a: $t)*) $(-> $r)?;)*
+            }
+
+            impl<H: $trait, T: __Delegate> __Delegate for $crate::Union<H, T> {
+                $(fn $name(active: $crate::Active<& $($mut)? Self> $(, $a: $t)*) $(-> $r)? {
+                    match active.split() {
+                        Ok(head) => <H as $trait>::$name(head $(, $a)*),
+                        Err(tail) => <T as __Delegate>::$name(tail $(, $a)*),
+                    }
+                })*
+            }
+
+            impl __Delegate for $crate::EmptyUnion {
+                $(fn $name(active: $crate::Active<& $($mut)? Self> $(, _: $t)*) $(-> $r)? {
+                    active.ex_falso()
+                })*
+            }
+
+            impl<T, Tag> $trait for $crate::Coproduct<T, Tag>
+            where
+                T: $crate::IndexedDrop + __Delegate,
+                Tag: $crate::Discriminant,
+            {
+                $(fn $name(& $($mut)? self $(, $a: $t)*) $(-> $r)? {
+                    <T as __Delegate>::$name($crate::Active::from(self) $(, $a)*)
+                })*
+            }
+
+            impl<T, Tag> $trait for $crate::CopyableCoproduct<T, Tag>
+            where
+                T: Copy + __Delegate,
+                Tag: $crate::Discriminant,
+            {
+                $(fn $name(& $($mut)? self $(, $a: $t)*) $(-> $r)? {
+                    <T as __Delegate>::$name($crate::Active::from(self) $(, $a)*)
+                })*
+            }
+        };
+    };
+
+    ($(#[$attr:meta])* $vis:vis trait $trait:ident { $($body:tt)* }) => {
+        $crate::delegate!(@parse ($(#[$attr])* $vis) $trait [] $($body)*);
+    };
+}
@@ -0,0 +1,144 @@
+use crate::Union;
+
+/// Declares sets of errors as [Coproduct](struct@crate::Coproduct) type aliases.
+///
+/// A set can contain sets declared earlier in the same invocation. They are
+/// flattened and duplicate members are removed, so every set is a coproduct
+/// of distinct leaf types. Types are compared as written, so the same type
+/// must always be spelled the same way.
+///
+/// No conversions need to be generated: a member converts into a set with
+/// `inject` and a subset with `embed`.
+/// ```
+/// # use coproduct::{error_set, Coproduct};
+/// # #[derive(Debug, PartialEq)]
+/// # struct Utf8Error;
+/// # #[derive(Debug, PartialEq)]
+/// # struct ParseError;
+/// error_set! {
+///     ReadError = std::io::Error | Utf8Error;
+///     /// Anything that can go wrong while loading the configuration.
+///     pub ConfigError = ReadError | ParseError | Utf8Error;
+/// }
+///
+/// let e: ConfigError = Coproduct::inject(ParseError);
+/// let r: ReadError = Coproduct::inject(Utf8Error);
+/// let e2: ConfigError = r.embed();
+/// assert!(e2.is::<_, Utf8Error>());
+///
+/// let _: Coproduct!(std::io::Error, Utf8Error, ParseError) = e;
+/// ```
+///
+/// Sets can only refer to sets declared in the same invocation. Members are
+/// paths without generic arguments; use a type alias for anything else.
+#[macro_export]
+macro_rules! error_set {
+    ($($input:tt)*) => {
+        $crate::__error_set!(@start [$] $($input)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __error_set {
+    (@start [$d:tt]
+        $($(#[$attr:meta])* $vis:vis $name:ident = $($($seg:ident)::+)|+;)*
+    ) => {
+        macro_rules! __error_set_flatten {
+            $(
+                ([[$name] $d($d todo:tt)*] $d acc:tt $d decl:tt) => {
+                    __error_set_flatten!([$([$($seg)::+])+ $d($d todo)*] $d acc $d decl);
+                };
+            )*
+            ([$d leaf:tt $d($d todo:tt)*] [$d($d acc:tt)*] $d decl:tt) => {
+                __error_set_flatten!([$d($d todo)*] [$d($d acc)* $d leaf] $d decl);
+            };
+            ([] $d acc:tt ($d($d decl:tt)*)) => {
+                $d($d decl)* = $crate::Coproduct<$crate::__error_set!(@dedup [] $d acc)>;
+            };
+        }
+
+        $(
+            __error_set_flatten!(
+                [$([$($seg)::+])+]
+                []
+                ($(#[$attr])* $vis type $name)
+            );
+        )*
+    };
+
+    (@dedup [$($seen:tt)*] []) => {
+        $crate::EmptyUnion
+    };
+    (@dedup [$($seen:tt)*] [[$($x:tt)*] $($rest:tt)*]) => {
+        <$crate::__private::Choose<
+            { !$crate::__private::contains(&[$(stringify!$seen),*], stringify!($($x)*)) },
+        > as $crate::__private::Cons<
+            $($x)*,
+            $crate::__error_set!(@dedup [$($seen)* [$($x)*]] [$($rest)*]),
+        >>::Out
+    };
+}
+
+/// Selects one of the implementations of [Cons].
+pub struct Choose<const B: bool>;
+
+/// Prepends X to a Union if Self is `Choose<true>`.
+pub trait Cons<X, T> {
+    type Out;
+}
+
+impl<X, T> Cons<X, T> for Choose<true> {
+    type Out = Union<X, T>;
+}
+
+impl<X, T> Cons<X, T> for Choose<false> {
+    type Out = T;
+}
+
+pub const fn contains(haystack: &[&str], needle: &str) -> bool {
+    let mut i = 0;
+    while i < haystack.len() {
+        if str_eq(haystack[i], needle) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Coproduct, MkUnion};
+
+    struct A;
+    struct B;
+    struct C;
+
+    error_set! {
+        Ab = A | B | A;
+        Abc = C | Ab | B;
+    }
+
+    #[test]
+    fn flatten_and_dedup() {
+        type Flat = Coproduct<MkUnion!(C, A, B)>;
+        let _: Coproduct<MkUnion!(A, B)> = Ab::inject(A);
+        let _: Flat = Abc::inject(B);
+    }
+}
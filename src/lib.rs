@@ -1,3 +1,4 @@
+#![cfg_attr(not(test), no_std)]
 #![cfg_attr(feature = "type_inequality_hack", feature(associated_const_equality))]
 #![cfg_attr(feature = "type_inequality_hack", feature(const_type_id))]
 //! Rust enums are coproducts but the datastructure provided in this library
@@ -15,27 +16,56 @@
 //! }
 //! ```
 //!
-//! The coproducts take as much memory as the largest variant and 32 bits
-//! for the tag, which is pretty close to optimal. They do not benefit from
+//! The coproducts take as much memory as the largest variant and a tag that
+//! is a single byte unless there are more than 255 variants (see
+//! [Discriminant]), which is pretty close to optimal. The tag is never zero,
+//! so wrapping a coproduct in an `Option` doesn't make it any bigger.
+//! They do not benefit from
 //! Rust's enum layout optimizations, but the whole reason for this crate is
 //! that those optimizations aren't perfect. Implementing a coproduct as nested
 //! enums akin to a purely functional list results in extremely high memory use.
-//! (Tested in Rust 1.66)
+//! (Tested in Rust 1.66) For storing many copyable coproducts compactly,
+//! [PackedCoproduct](struct@PackedCoproduct) gets rid of padding as well.
 //!
 //! Another benefit is that the implementation of some functions is a lot simpler
 //! when there is no need to pretend that a nested structure is traversed. The
 //! downside is that unlike the coproduct provided by frunk, this library uses
 //! unsafe.
+//!
+//! The crate is `no_std`. Conversions into boxed trait objects require the
+//! `alloc` feature, which is enabled by the default `std` feature.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+mod active;
 mod coproduct;
 mod count;
+mod delegate;
+mod error_set;
+mod matcher;
+mod product;
 mod public_traits;
+mod result;
+mod table;
+mod tag;
 mod union;
 
 pub use crate::coproduct::*;
+pub use active::Active;
 pub use count::*;
+pub use matcher::Matcher;
+pub use product::{HCons, HNil, ProductAt, Sculpt};
 pub use public_traits::*;
-pub use union::{EmptyUnion, Union};
+pub use result::ResultExt;
+pub use tag::{Discriminant, TagFor};
+#[cfg(feature = "alloc")]
+pub use union::IndexedDynBox;
+pub use union::{
+    EmptyUnion, IndexedClone, IndexedDebug, IndexedDisplay, IndexedDyn, IndexedEq, IndexedError,
+    IndexedFold, IndexedHash, IndexedInto, IndexedOrd, IndexedPartialOrd, IndexedTotalEq, Pack,
+    PackedUnion, Union,
+};
 
 #[cfg(feature = "type_inequality_hack")]
 mod type_inequality;
@@ -45,3 +75,11 @@ pub use type_inequality::NotEqual;
 pub mod merge;
 #[cfg(feature = "type_inequality_hack")]
 pub use merge::Merge;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::error_set::{contains, Choose, Cons};
+    pub use crate::tag::{Fits, Smallest};
+    #[cfg(feature = "alloc")]
+    pub use alloc::boxed::Box;
+}
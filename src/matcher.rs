@@ -0,0 +1,203 @@
+use crate::{At, Coproduct, CopyableCoproduct, Discriminant, EmptyUnion};
+
+/// Exhaustive case analysis on a coproduct.
+///
+/// Created by the `matcher` method of [Coproduct](struct@Coproduct) and
+/// [CopyableCoproduct](struct@CopyableCoproduct).
+/// Every call to [Matcher::on] handles one variant. [Matcher::finish] can
+/// only be called once all variants have been handled.
+/// ```
+/// # use coproduct::Coproduct;
+/// struct Cat;
+/// struct Dog(&'static str);
+///
+/// let animal: Coproduct!(Cat, Dog) = Coproduct::inject(Dog("Sparky"));
+/// let name = animal
+///     .matcher()
+///     .on(|_: Cat| "a cat")
+///     .on(|Dog(name)| name)
+///     .finish();
+/// assert_eq!(name, "Sparky");
+/// ```
+///
+/// Forgetting a variant is a compile error:
+/// ```compile_fail
+/// # use coproduct::Coproduct;
+/// # struct Cat;
+/// # struct Dog;
+/// let animal: Coproduct!(Cat, Dog) = Coproduct::inject(Dog);
+/// animal.matcher().on(|_: Cat| ()).finish();
+/// ```
+#[must_use]
+pub struct Matcher<C, R>(Result<R, C>);
+
+impl<C, R> Matcher<C, R> {
+    pub(crate) fn new(c: C) -> Self {
+        Matcher(Err(c))
+    }
+
+    /// Handle the case where the coproduct contains an X.
+    pub fn on<I, X, F>(self, f: F) -> Matcher<C::Pruned, R>
+    where
+        C: At<I, X>,
+        F: FnOnce(X) -> R,
+    {
+        Matcher(match self.0 {
+            Ok(r) => Ok(r),
+            Err(c) => match c.uninject() {
+                Ok(x) => Ok(f(x)),
+                Err(rest) => Err(rest),
+            },
+        })
+    }
+}
+
+impl<R, Tag: Discriminant> Matcher<Coproduct<EmptyUnion, Tag>, R> {
+    /// Returns the result of the handler that was called.
+    pub fn finish(self) -> R {
+        self.0.unwrap_or_else(|c| c.ex_falso())
+    }
+}
+
+impl<R, Tag: Discriminant> Matcher<CopyableCoproduct<EmptyUnion, Tag>, R> {
+    /// Returns the result of the handler that was called.
+    pub fn finish(self) -> R {
+        self.0.unwrap_or_else(|c| c.ex_falso())
+    }
+}
+
+/// Case analysis on a coproduct with syntax similar to `match`.
+///
+/// Each arm either binds a variant with a type ascription (`x: A`, or
+/// `mut x: A` and `ref x: A` like in a `let`) or
+/// destructures a tuple or struct pattern whose type is given by its path
+/// (`Dog(name)`, `Point { x, y }`). The arms are tried in order. The last
+/// arm may be a bare identifier or `_`, which receives the coproduct of the
+/// variants that weren't handled.
+///
+/// Without such a catch-all arm, leaving out a variant is a compile error.
+/// Because a bare identifier is a catch-all, unit structs need to be matched
+/// as `_: Cat`.
+/// ```
+/// # use coproduct::{match_coproduct, Coproduct};
+/// struct Cat;
+/// struct Dog(&'static str);
+/// struct Fish {
+///     fins: u8,
+/// }
+///
+/// let animal: Coproduct!(Cat, Dog, Fish) = Coproduct::inject(Fish { fins: 7 });
+/// let legs = match_coproduct!(animal {
+///     _: Cat => 4,
+///     Dog(_) => 4,
+///     Fish { fins } => {
+///         assert_eq!(fins, 7);
+///         0
+///     }
+/// });
+/// assert_eq!(legs, 0);
+///
+/// let animal: Coproduct!(Cat, Dog, Fish) = Coproduct::inject(Dog("Sparky"));
+/// let name = match_coproduct!(animal {
+///     Dog(name) => name,
+///     rest => {
+///         assert!(rest.uninject::<_, Fish>().is_err());
+///         "?"
+///     }
+/// });
+/// assert_eq!(name, "Sparky");
+/// ```
+///
+/// ```compile_fail
+/// # use coproduct::{match_coproduct, Coproduct};
+/// # struct Cat;
+/// # struct Dog;
+/// let animal: Coproduct!(Cat, Dog) = Coproduct::inject(Dog);
+/// match_coproduct!(animal { _: Cat => () });
+/// ```
+///
+/// A catch-all arm that isn't the last one is rejected as well.
+/// ```compile_fail
+/// # use coproduct::{match_coproduct, Coproduct};
+/// # struct Cat;
+/// # struct Dog;
+/// let animal: Coproduct!(Cat, Dog) = Coproduct::inject(Dog);
+/// match_coproduct!(animal { _ => (), _: Cat => () });
+/// ```
+#[macro_export]
+macro_rules! match_coproduct {
+    ($($input:tt)+) => {
+        $crate::__match_coproduct!(@scrutinee [] $($input)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __match_coproduct {
+    (@scrutinee [$($c:tt)*] { $($arms:tt)* }) => {{
+        let coproduct = $($c)*;
+        $crate::__match_coproduct!(@arms coproduct; $($arms)*)
+    }};
+    (@scrutinee [$($c:tt)*] $next:tt $($rest:tt)+) => {
+        $crate::__match_coproduct!(@scrutinee [$($c)* $next] $($rest)+)
+    };
+
+    (@arms $c:ident;) => {
+        $c.ex_falso()
+    };
+    (@arms $c:ident; mut $x:ident : $t:ty => $($rest:tt)*) => {
+        $crate::__match_coproduct!(@body $c; (mut $x); ($t); $($rest)*)
+    };
+    (@arms $c:ident; ref mut $x:ident : $t:ty => $($rest:tt)*) => {
+        $crate::__match_coproduct!(@body $c; (ref mut $x); ($t); $($rest)*)
+    };
+    (@arms $c:ident; ref $x:ident : $t:ty => $($rest:tt)*) => {
+        $crate::__match_coproduct!(@body $c; (ref $x); ($t); $($rest)*)
+    };
+    (@arms $c:ident; $x:tt : $t:ty => $($rest:tt)*) => {
+        $crate::__match_coproduct!(@body $c; ($x); ($t); $($rest)*)
+    };
+    (@arms $c:ident; $($p:ident)::+ ( $($inner:tt)* ) => $($rest:tt)*) => {
+        $crate::__match_coproduct!(@body $c; ($($p)::+ ( $($inner)* )); ($($p)::+); $($rest)*)
+    };
+    (@arms $c:ident; $($p:ident)::+ { $($inner:tt)* } => $($rest:tt)*) => {
+        $crate::__match_coproduct!(@body $c; ($($p)::+ { $($inner)* }); ($($p)::+); $($rest)*)
+    };
+    (@arms $c:ident; $r:tt => { $($body:tt)* } $(,)?) => {{
+        let $r = $c;
+        $($body)*
+    }};
+    (@arms $c:ident; $r:tt => $body:expr $(,)?) => {{
+        let $r = $c;
+        $body
+    }};
+    (@arms $c:ident; $($rest:tt)*) => {
+        compile_error!(
+            "expected `name: Type`, a tuple or struct pattern, or a catch-all as the last arm"
+        )
+    };
+
+    (@body $c:ident; $pat:tt; $t:tt; { $($body:tt)* }, $($rest:tt)*) => {
+        $crate::__match_coproduct!(@on $c; $pat; $t; { $($body)* }; $($rest)*)
+    };
+    (@body $c:ident; $pat:tt; $t:tt; { $($body:tt)* } $($rest:tt)*) => {
+        $crate::__match_coproduct!(@on $c; $pat; $t; { $($body)* }; $($rest)*)
+    };
+    (@body $c:ident; $pat:tt; $t:tt; $body:expr, $($rest:tt)*) => {
+        $crate::__match_coproduct!(@on $c; $pat; $t; $body; $($rest)*)
+    };
+    (@body $c:ident; $pat:tt; $t:tt; $body:expr) => {
+        $crate::__match_coproduct!(@on $c; $pat; $t; $body;)
+    };
+
+    (@on $c:ident; ($($pat:tt)*); ($($t:tt)*); $body:expr; $($rest:tt)*) => {
+        match $c.uninject::<_, $($t)*>() {
+            Ok($($pat)*) => $body,
+            Err(remaining) => $crate::__match_coproduct!(@arms remaining; $($rest)*),
+        }
+    };
+
+    ($($input:tt)*) => {
+        compile_error!("expected a coproduct followed by a block of arms")
+    };
+}
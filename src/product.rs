@@ -0,0 +1,188 @@
+use crate::{Here, There};
+
+/// A heterogeneous list: a product of H and the types in T.
+///
+/// Use [Product!](crate::Product!) to write the type and [hlist!](crate::hlist!)
+/// to build values. Fields are looked up by type using the same indices as
+/// coproduct variants.
+/// ```
+/// # use coproduct::{hlist, Product};
+/// let p: Product!(u8, &str, bool) = hlist![1, "two", true];
+/// assert_eq!(p.get::<_, &str>(), &"two");
+///
+/// let (b, rest) = p.pluck::<_, bool>();
+/// assert!(b);
+/// assert_eq!(rest, hlist![1, "two"]);
+///
+/// let (reordered, rest): (Product!(&str, u8), _) = rest.sculpt();
+/// assert_eq!(reordered, hlist!["two", 1]);
+/// assert_eq!(rest, hlist![]);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HCons<H, T> {
+    pub head: H,
+    pub tail: T,
+}
+
+/// The empty heterogeneous list.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HNil;
+
+/// Builds a heterogeneous list out of the given values.
+#[macro_export]
+macro_rules! hlist {
+    () => ($crate::HNil);
+    ($h:expr $(, $t:expr)* $(,)?) => (
+        $crate::HCons {
+            head: $h,
+            tail: $crate::hlist!($($t),*),
+        }
+    );
+}
+
+/// Builds the type of a heterogeneous list containing the given types.
+#[macro_export]
+macro_rules! Product {
+    () => ($crate::HNil);
+    ($h:ty $(, $t:ty)* $(,)?) => (
+        $crate::HCons<$h, $crate::Product!($($t),*)>
+    );
+}
+
+/// This trait is implemented for products where field I has type X.
+pub trait ProductAt<I, X> {
+    fn get(&self) -> &X;
+
+    fn get_mut(&mut self) -> &mut X;
+
+    /// Take out X, returning it along with the other fields.
+    fn pluck(self) -> (X, Self::Pruned);
+
+    /// The product minus its Ith field
+    type Pruned;
+}
+
+impl<X, Rest> ProductAt<Here, X> for HCons<X, Rest> {
+    fn get(&self) -> &X {
+        &self.head
+    }
+
+    fn get_mut(&mut self) -> &mut X {
+        &mut self.head
+    }
+
+    fn pluck(self) -> (X, Rest) {
+        (self.head, self.tail)
+    }
+
+    type Pruned = Rest;
+}
+
+impl<I, X, H, T> ProductAt<There<I>, X> for HCons<H, T>
+where
+    T: ProductAt<I, X>,
+{
+    fn get(&self) -> &X {
+        self.tail.get()
+    }
+
+    fn get_mut(&mut self) -> &mut X {
+        self.tail.get_mut()
+    }
+
+    fn pluck(self) -> (X, Self::Pruned) {
+        let (x, tail) = self.tail.pluck();
+        (
+            x,
+            HCons {
+                head: self.head,
+                tail,
+            },
+        )
+    }
+
+    type Pruned = HCons<H, T::Pruned>;
+}
+
+/// Implemented on products that contain all the fields of Target.
+pub trait Sculpt<Target, Indices> {
+    type Remainder;
+
+    /// Rearrange the product into Target. Returns the fields that were left over.
+    fn sculpt(self) -> (Target, Self::Remainder);
+}
+
+impl<Source> Sculpt<HNil, HNil> for Source {
+    type Remainder = Source;
+
+    #[inline(always)]
+    fn sculpt(self) -> (HNil, Source) {
+        (HNil, self)
+    }
+}
+
+impl<Source, TH, TT, IH, IT> Sculpt<HCons<TH, TT>, HCons<IH, IT>> for Source
+where
+    Source: ProductAt<IH, TH>,
+    Source::Pruned: Sculpt<TT, IT>,
+{
+    type Remainder = <Source::Pruned as Sculpt<TT, IT>>::Remainder;
+
+    fn sculpt(self) -> (HCons<TH, TT>, Self::Remainder) {
+        let (head, rest) = self.pluck();
+        let (tail, remainder) = rest.sculpt();
+        (HCons { head, tail }, remainder)
+    }
+}
+
+impl<H, T> HCons<H, T> {
+    /// Returns a reference to the field of type X.
+    pub fn get<I, X>(&self) -> &X
+    where
+        Self: ProductAt<I, X>,
+    {
+        <Self as ProductAt<I, X>>::get(self)
+    }
+
+    /// Returns a mutable reference to the field of type X.
+    pub fn get_mut<I, X>(&mut self) -> &mut X
+    where
+        Self: ProductAt<I, X>,
+    {
+        <Self as ProductAt<I, X>>::get_mut(self)
+    }
+
+    /// Take out the field of type X, returning it along with the other fields.
+    pub fn pluck<I, X>(self) -> (X, <Self as ProductAt<I, X>>::Pruned)
+    where
+        Self: ProductAt<I, X>,
+    {
+        <Self as ProductAt<I, X>>::pluck(self)
+    }
+
+    /// Rearrange the product into Target, which may have fewer fields.
+    /// Returns the fields that were left over.
+    pub fn sculpt<Target, Indices>(self) -> (Target, <Self as Sculpt<Target, Indices>>::Remainder)
+    where
+        Self: Sculpt<Target, Indices>,
+    {
+        <Self as Sculpt<Target, Indices>>::sculpt(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn get_pluck_sculpt() {
+        let mut p: Product!(u8, String, bool) = hlist![1, "hi".to_string(), false];
+        *p.get_mut::<_, bool>() = true;
+        assert_eq!(p.get::<_, String>(), "hi");
+
+        let (s, rest) = p.pluck::<_, String>();
+        assert_eq!(s, "hi");
+
+        let (picked, rest): (Product!(bool), _) = rest.sculpt();
+        assert_eq!(picked, hlist![true]);
+        assert_eq!(rest, hlist![1u8]);
+    }
+}
@@ -1,9 +1,23 @@
+use crate::table::{DropOp, FnTable};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
 /// Trait for properly deallocating Unions that are not Copy.
 ///
-/// Unlike the other Indexed* traits, this one is exported because there is no
-/// way to avoid mentioning it. For example to require IndexedClone it suffices
-/// to require that `Coproduct<T>` is [Clone].
-pub trait IndexedDrop {
+/// Like the other Indexed* traits, it is implemented by recursion over
+/// [Union](crate::Union). See [Active](crate::Active) for defining similar
+/// operations without unsafe code.
+///
+/// Dropping, cloning, formatting with Debug and comparing for equality are
+/// frequent enough that the recursion only builds a table of functions at
+/// compile time. In optimised builds, calling the one for the active variant
+/// takes the same time no matter how many variants there are.
+///
+/// The table is an implementation detail, so this trait is sealed. It is
+/// implemented for every [Union](crate::Union) and cannot be implemented
+/// outside this crate. The same goes for [IndexedClone](crate::IndexedClone),
+/// [IndexedDebug](crate::IndexedDebug) and [IndexedEq](crate::IndexedEq).
+pub trait IndexedDrop: FnTable<DropOp> {
     /// # Safety
     /// The argument `i` must be the index of the active variant
     /// of the Union.
@@ -24,3 +38,147 @@ pub trait UnionAt<I, X> {
     /// The coproduct minus its Ith variant
     type Pruned;
 }
+
+/// Trait for borrowing the active variant of a Union.
+///
+/// Needed to name the coproduct of references returned by `as_ref`
+/// and `as_mut`.
+pub trait IndexedRef<'a> {
+    /// The Union with every variant replaced by a shared reference to it.
+    type Ref: Copy + IndexedDrop + 'a;
+    /// The Union with every variant replaced by a mutable reference to it.
+    type Mut: IndexedDrop + 'a;
+
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn iref(&'a self, i: u32) -> Self::Ref;
+
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn imut(&'a mut self, i: u32) -> Self::Mut;
+}
+
+/// A function that can be applied to values of different types.
+///
+/// Implement it for every variant type of a coproduct to transform all
+/// variants at once with `map_all`.
+/// ```
+/// # use coproduct::{Coproduct, Poly};
+/// struct Boxer;
+///
+/// impl<X> Poly<X> for Boxer {
+///     type Output = Box<X>;
+///     fn call(&mut self, x: X) -> Box<X> {
+///         Box::new(x)
+///     }
+/// }
+///
+/// let c: Coproduct!(u8, String) = Coproduct::inject(1u8);
+/// let boxed: Coproduct!(Box<u8>, Box<String>) = c.map_all(Boxer);
+/// assert_eq!(boxed.uninject::<_, Box<u8>>(), Ok(Box::new(1)));
+/// ```
+pub trait Poly<X> {
+    type Output;
+
+    fn call(&mut self, x: X) -> Self::Output;
+}
+
+/// Trait for applying a [Poly] to the active variant of a Union.
+pub trait IndexedMap<F> {
+    /// The Union with every variant replaced by the result of F.
+    type Output: IndexedDrop;
+
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn imap(self, f: &mut F, i: u32) -> Self::Output;
+}
+
+/// Implemented on trait objects for the types that can be coerced into them.
+///
+/// Used by `as_dyn` and `as_dyn_mut`. Implement it for
+/// your own traits with [impl_dyn_from!](crate::impl_dyn_from!).
+pub trait DynFrom<T> {
+    fn from_ref(x: &T) -> &Self;
+
+    fn from_mut(x: &mut T) -> &mut Self;
+}
+
+/// Implemented on trait objects for the types that can be coerced into them
+/// when boxed.
+///
+/// Used by `into_boxed_dyn`. Requires the `alloc` feature.
+/// [impl_dyn_from!](crate::impl_dyn_from!) implements it along with
+/// [DynFrom] when the feature is enabled.
+#[cfg(feature = "alloc")]
+pub trait DynFromBox<T>: DynFrom<T> {
+    fn from_box(x: Box<T>) -> Box<Self>;
+}
+
+/// Implements [DynFrom] for `dyn Trait` and every type that implements Trait.
+/// ```
+/// # use coproduct::{impl_dyn_from, Coproduct};
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// struct Square(f64);
+/// impl Shape for Square {
+///     fn area(&self) -> f64 {
+///         self.0 * self.0
+///     }
+/// }
+///
+/// struct Circle(f64);
+/// impl Shape for Circle {
+///     fn area(&self) -> f64 {
+///         3.0 * self.0 * self.0
+///     }
+/// }
+///
+/// impl_dyn_from!(Shape);
+///
+/// let c: Coproduct!(Square, Circle) = Coproduct::inject(Square(2.0));
+/// assert_eq!(c.as_dyn::<dyn Shape>().area(), 4.0);
+/// ```
+#[macro_export]
+macro_rules! impl_dyn_from {
+    ($($bound:tt)+) => {
+        impl<'a, T: $($bound)+ + 'a> $crate::DynFrom<T> for dyn $($bound)+ + 'a {
+            fn from_ref(x: &T) -> &Self {
+                x
+            }
+
+            fn from_mut(x: &mut T) -> &mut Self {
+                x
+            }
+        }
+
+        $crate::__dyn_from_box!($($bound)+);
+    };
+}
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dyn_from_box {
+    ($($bound:tt)+) => {
+        impl<'a, T: $($bound)+ + 'a> $crate::DynFromBox<T> for dyn $($bound)+ + 'a {
+            fn from_box(x: $crate::__private::Box<T>) -> $crate::__private::Box<Self> {
+                x
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __dyn_from_box {
+    ($($bound:tt)+) => {};
+}
+
+impl_dyn_from!(core::fmt::Debug);
+impl_dyn_from!(core::fmt::Display);
@@ -0,0 +1,160 @@
+use crate::{
+    At, Coproduct, CopyableCoproduct, Discriminant, Embed, EmptyUnion, IntoCoproduct, Split,
+};
+use core::convert::Infallible;
+
+/// Methods for results whose error type is a coproduct.
+/// ```
+/// # use coproduct::{Coproduct, ResultExt};
+/// #[derive(Debug)]
+/// struct NotFound;
+/// #[derive(Debug)]
+/// struct Timeout;
+///
+/// fn fetch() -> Result<u32, Coproduct!(NotFound)> {
+///     Err(Coproduct::inject(NotFound))
+/// }
+///
+/// fn fetch_or_default() -> Result<u32, Coproduct!(Timeout, NotFound)> {
+///     fetch().embed_err()
+/// }
+///
+/// let n = fetch_or_default().handle_err(|NotFound| 0);
+/// let n = n.handle_err(|Timeout| 1).into_ok();
+/// assert_eq!(n, 0);
+/// ```
+pub trait ResultExt<T, C> {
+    /// Convert the error into a coproduct with more variants.
+    fn embed_err<Target, Indices>(self) -> Result<T, Target>
+    where
+        C: Embed<Target, Indices>;
+
+    /// Convert the error into the coproduct Target. The error can either be
+    /// one of the variants of Target or a coproduct that embeds into it.
+    ///
+    /// Use this before `?` to return errors of a function with a coproduct
+    /// as its error type.
+    /// ```
+    /// # use coproduct::{Coproduct, ResultExt};
+    /// # #[derive(Debug)]
+    /// # struct ParseError;
+    /// # #[derive(Debug)]
+    /// # struct RangeError;
+    /// fn parse(s: &str) -> Result<u32, ParseError> {
+    ///     s.parse().map_err(|_| ParseError)
+    /// }
+    ///
+    /// fn check(x: u32) -> Result<u32, Coproduct!(RangeError)> {
+    ///     if x < 10 { Ok(x) } else { Err(Coproduct::inject(RangeError)) }
+    /// }
+    ///
+    /// fn parse_digit(s: &str) -> Result<u32, Coproduct!(ParseError, RangeError)> {
+    ///     let x = parse(s).inject_err()?;
+    ///     check(x).inject_err()
+    /// }
+    ///
+    /// assert!(parse_digit("x").unwrap_err().is::<_, ParseError>());
+    /// assert!(parse_digit("10").unwrap_err().is::<_, RangeError>());
+    /// assert_eq!(parse_digit("7").unwrap(), 7);
+    /// ```
+    fn inject_err<Target, Index>(self) -> Result<T, Target>
+    where
+        C: IntoCoproduct<Target, Index>;
+
+    /// Recover from errors of type E. The remaining error cannot be an E.
+    fn handle_err<I, E, F>(self, f: F) -> Result<T, C::Pruned>
+    where
+        C: At<I, E>,
+        F: FnOnce(E) -> T;
+
+    /// Separate the errors that fit in the coproduct Sub from the rest.
+    /// Those end up in the inner result, the others stay in the outer one.
+    /// ```
+    /// # use coproduct::{Coproduct, ResultExt};
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct NotFound;
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct Timeout;
+    /// # #[derive(Debug, PartialEq)]
+    /// # struct Denied;
+    /// fn fetch() -> Result<u32, Coproduct!(NotFound, Timeout, Denied)> {
+    ///     Err(Coproduct::inject(Timeout))
+    /// }
+    ///
+    /// let retry = fetch().narrow_err::<Coproduct!(Timeout), _>();
+    /// let retry = retry.unwrap().unwrap_err();
+    /// assert_eq!(retry.uninject::<_, Timeout>().ok(), Some(Timeout));
+    ///
+    /// let denied: Result<u32, Coproduct!(NotFound, Timeout, Denied)> =
+    ///     Err(Coproduct::inject(Denied));
+    /// let rest = denied.narrow_err::<Coproduct!(Timeout), _>().unwrap_err();
+    /// assert!(rest.is::<_, Denied>());
+    /// ```
+    fn narrow_err<Sub, Indices>(self) -> Result<Result<T, Sub>, C::Remainder>
+    where
+        C: Split<Sub, Indices>;
+
+    /// Unwrap a result that cannot be an error.
+    fn into_ok(self) -> T
+    where
+        C: Into<Infallible>;
+}
+
+impl<T, C> ResultExt<T, C> for Result<T, C> {
+    fn embed_err<Target, Indices>(self) -> Result<T, Target>
+    where
+        C: Embed<Target, Indices>,
+    {
+        self.map_err(C::embed)
+    }
+
+    fn inject_err<Target, Index>(self) -> Result<T, Target>
+    where
+        C: IntoCoproduct<Target, Index>,
+    {
+        self.map_err(C::into_coproduct)
+    }
+
+    fn handle_err<I, E, F>(self, f: F) -> Result<T, C::Pruned>
+    where
+        C: At<I, E>,
+        F: FnOnce(E) -> T,
+    {
+        match self {
+            Ok(t) => Ok(t),
+            Err(c) => c.uninject().map(f),
+        }
+    }
+
+    fn narrow_err<Sub, Indices>(self) -> Result<Result<T, Sub>, C::Remainder>
+    where
+        C: Split<Sub, Indices>,
+    {
+        match self {
+            Ok(t) => Ok(Ok(t)),
+            Err(c) => c.split().map(Err),
+        }
+    }
+
+    fn into_ok(self) -> T
+    where
+        C: Into<Infallible>,
+    {
+        match self.map_err(C::into) {
+            Ok(t) => t,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl<Tag: Discriminant> From<Coproduct<EmptyUnion, Tag>> for Infallible {
+    fn from(c: Coproduct<EmptyUnion, Tag>) -> Self {
+        c.ex_falso()
+    }
+}
+
+impl<Tag: Discriminant> From<CopyableCoproduct<EmptyUnion, Tag>> for Infallible {
+    fn from(c: CopyableCoproduct<EmptyUnion, Tag>) -> Self {
+        c.ex_falso()
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{EmptyUnion, Union};
+use core::fmt::{Debug, Formatter, Result};
+
+/// A row of a table built at compile time. Arrays can't be used, as their
+/// length can't depend on a type parameter.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Row<X, Rest>(pub X, pub Rest);
+
+/// Reads entry `i` of a table made of [Row]s of X.
+///
+/// The table is passed by value, because a reference to a constant of a
+/// generic type can't be taken. In optimised builds inlining turns this into
+/// a single read from the constant. Unoptimised builds copy the whole table
+/// onto the stack first, so each lookup takes time proportional to the
+/// number of rows. `cargo bench --profile dev` measures the difference.
+/// # Safety
+/// `i` must be less than the number of rows.
+#[inline(always)]
+pub unsafe fn lookup<Table: Copy, X: Copy>(table: Table, i: u32) -> X {
+    *(&table as *const Table as *const X).add(i as usize)
+}
+
+/// An operation that is dispatched on the active variant of a Union by
+/// calling a function from a table.
+///
+/// The functions take pointers to the Union. That is the same as a pointer
+/// to any of its variants, so the table of the tail of a Union can be reused.
+pub trait Operation {
+    type Fn: Copy;
+}
+
+/// Implemented on Unions, providing the function for each variant.
+///
+/// This module is private, so as a supertrait this seals the traits that
+/// dispatch through tables.
+pub trait FnTable<Op: Operation> {
+    type Table: Copy;
+    const TABLE: Self::Table;
+}
+
+impl<Op: Operation> FnTable<Op> for EmptyUnion {
+    type Table = ();
+    const TABLE: () = ();
+}
+
+/// The function for variant `i` of U.
+/// # Safety
+/// `i` must be less than the number of variants.
+#[inline(always)]
+pub unsafe fn function<U: FnTable<Op>, Op: Operation>(i: u32) -> Op::Fn {
+    lookup(U::TABLE, i)
+}
+
+pub struct DropOp;
+
+impl Operation for DropOp {
+    type Fn = unsafe fn(*mut ());
+}
+
+unsafe fn drop_variant<X>(x: *mut ()) {
+    core::ptr::drop_in_place(x as *mut X)
+}
+
+impl<H, T: FnTable<DropOp>> FnTable<DropOp> for Union<H, T> {
+    type Table = Row<<DropOp as Operation>::Fn, T::Table>;
+    const TABLE: Self::Table = Row(drop_variant::<H>, T::TABLE);
+}
+
+pub struct CloneOp;
+
+impl Operation for CloneOp {
+    type Fn = unsafe fn(*const (), *mut ());
+}
+
+unsafe fn clone_variant<X: Clone>(x: *const (), out: *mut ()) {
+    (out as *mut X).write((*(x as *const X)).clone())
+}
+
+impl<H: Clone, T: FnTable<CloneOp>> FnTable<CloneOp> for Union<H, T> {
+    type Table = Row<<CloneOp as Operation>::Fn, T::Table>;
+    const TABLE: Self::Table = Row(clone_variant::<H>, T::TABLE);
+}
+
+pub struct DebugOp;
+
+impl Operation for DebugOp {
+    type Fn = unsafe fn(*const (), &mut Formatter<'_>) -> Result;
+}
+
+unsafe fn debug_variant<X: Debug>(x: *const (), f: &mut Formatter<'_>) -> Result {
+    (*(x as *const X)).fmt(f)
+}
+
+impl<H: Debug, T: FnTable<DebugOp>> FnTable<DebugOp> for Union<H, T> {
+    type Table = Row<<DebugOp as Operation>::Fn, T::Table>;
+    const TABLE: Self::Table = Row(debug_variant::<H>, T::TABLE);
+}
+
+pub struct EqOp;
+
+impl Operation for EqOp {
+    type Fn = unsafe fn(*const (), *const ()) -> bool;
+}
+
+unsafe fn eq_variant<X: PartialEq>(x: *const (), y: *const ()) -> bool {
+    *(x as *const X) == *(y as *const X)
+}
+
+impl<H: PartialEq, T: FnTable<EqOp>> FnTable<EqOp> for Union<H, T> {
+    type Table = Row<<EqOp as Operation>::Fn, T::Table>;
+    const TABLE: Self::Table = Row(eq_variant::<H>, T::TABLE);
+}
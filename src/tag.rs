@@ -0,0 +1,173 @@
+use crate::count::Count;
+use core::{
+    fmt::Debug,
+    hash::Hash,
+    num::{NonZeroU16, NonZeroU32, NonZeroU8},
+};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An integer type that stores which variant of a coproduct is active.
+///
+/// Implemented for `u8`, `u16`, `u32` and their `NonZero` counterparts.
+/// The `NonZero` tags store the index plus one, which leaves a niche that
+/// `Option` and other enums wrapping the coproduct use for their own tag.
+/// They can hold one variant fewer than the plain integers.
+///
+/// The `Coproduct!` and `CopyableCoproduct!` macros pick the smallest
+/// `NonZero` tag that fits the number of variants. When naming the types
+/// directly, the tag defaults to `NonZeroU8` and a different one can be
+/// given as the second type parameter.
+/// ```
+/// # use coproduct::{CopyableCoproduct, MkUnion};
+/// use core::mem::size_of;
+///
+/// assert_eq!(size_of::<CopyableCoproduct!(u8, bool)>(), 2);
+/// assert_eq!(size_of::<Option<CopyableCoproduct!(u8, bool)>>(), 2);
+/// assert_eq!(size_of::<CopyableCoproduct<MkUnion!(u8, bool), u32>>(), 8);
+/// ```
+///
+/// Using a tag that is too small for the index of a variant is a compile
+/// error, not a truncation.
+pub trait Discriminant: Copy + Eq + Ord + Hash + Debug + sealed::Sealed {
+    /// The largest index that fits in the tag.
+    const MAX_INDEX: u32;
+
+    /// The tag of the variant at index I.
+    fn of<I: Count>() -> Self;
+
+    /// The tag of the variant at index `i`.
+    ///
+    /// # Safety
+    /// `i` must not be bigger than [MAX_INDEX](Self::MAX_INDEX).
+    #[doc(hidden)]
+    unsafe fn from_index_unchecked(i: u32) -> Self;
+
+    /// The index of the variant this tag refers to.
+    fn index(self) -> u32;
+}
+
+/// Implemented on indices, providing their value as a tag.
+///
+/// Evaluating [TAG](TagFor::TAG) for an index that is too big for the tag
+/// type fails at compile time.
+pub trait TagFor<Tag> {
+    const TAG: Tag;
+}
+
+macro_rules! impl_discriminant {
+    ($($t:ty),+) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl<I: Count> TagFor<$t> for I {
+                const TAG: $t = {
+                    assert!(
+                        I::INDEX <= <$t>::MAX as u32,
+                        "variant index does not fit in the tag type"
+                    );
+                    I::INDEX as $t
+                };
+            }
+
+            impl Discriminant for $t {
+                const MAX_INDEX: u32 = <$t>::MAX as u32;
+
+                #[inline(always)]
+                fn of<I: Count>() -> Self {
+                    <I as TagFor<$t>>::TAG
+                }
+
+                #[inline(always)]
+                unsafe fn from_index_unchecked(i: u32) -> Self {
+                    i as $t
+                }
+
+                #[inline(always)]
+                fn index(self) -> u32 {
+                    self as u32
+                }
+            }
+        )+
+    };
+}
+
+impl_discriminant!(u8, u16, u32);
+
+macro_rules! impl_nonzero_discriminant {
+    ($($t:ty: $int:ty),+) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl<I: Count> TagFor<$t> for I {
+                const TAG: $t = {
+                    assert!(
+                        I::INDEX < <$int>::MAX as u32,
+                        "variant index does not fit in the tag type"
+                    );
+                    match <$t>::new(I::INDEX as $int + 1) {
+                        Some(tag) => tag,
+                        None => unreachable!(),
+                    }
+                };
+            }
+
+            impl Discriminant for $t {
+                const MAX_INDEX: u32 = <$int>::MAX as u32 - 1;
+
+                #[inline(always)]
+                fn of<I: Count>() -> Self {
+                    <I as TagFor<$t>>::TAG
+                }
+
+                #[inline(always)]
+                unsafe fn from_index_unchecked(i: u32) -> Self {
+                    <$t>::new_unchecked(i as $int + 1)
+                }
+
+                #[inline(always)]
+                fn index(self) -> u32 {
+                    self.get() as u32 - 1
+                }
+            }
+        )+
+    };
+}
+
+impl_nonzero_discriminant!(NonZeroU8: u8, NonZeroU16: u16, NonZeroU32: u32);
+
+/// Selects the smallest tag for a number of variants, given whether
+/// it fits in a `u8` and whether it fits in a `u16`.
+#[doc(hidden)]
+pub struct Fits<const U8: bool, const U16: bool>;
+
+#[doc(hidden)]
+pub trait Smallest {
+    type Tag: Discriminant;
+}
+
+impl Smallest for Fits<true, true> {
+    type Tag = NonZeroU8;
+}
+
+impl Smallest for Fits<false, true> {
+    type Tag = NonZeroU16;
+}
+
+impl Smallest for Fits<false, false> {
+    type Tag = NonZeroU32;
+}
+
+/// The smallest tag for the given types.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __smallest_tag {
+    ( $( $t:ty ),+ ) => (
+        <$crate::__private::Fits<
+            { [$( stringify!($t) ),+].len() < 0x100 },
+            { [$( stringify!($t) ),+].len() < 0x10000 },
+        > as $crate::__private::Smallest>::Tag
+    );
+}
@@ -1,6 +1,15 @@
-use core::mem::ManuallyDrop;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::cmp::Ordering;
+use core::error::Error;
+use core::hash::{Hash, Hasher};
+use core::mem::{ManuallyDrop, MaybeUninit};
 
-use crate::{public_traits::*, Here, There};
+use crate::{
+    public_traits::*,
+    table::{function, CloneOp, DebugOp, DropOp, EqOp, FnTable},
+    HCons, HNil, Here, There,
+};
 
 #[repr(C)]
 pub union Union<A, B> {
@@ -28,8 +37,34 @@ impl<A: Copy, B: Copy> Clone for Union<A, B> {
 
 impl<A: Copy, B: Copy> Copy for Union<A, B> {}
 
+/// A [Union] of Copy types without any padding.
+///
+/// Its alignment is 1, so it is as big as its largest variant but its
+/// variants can't be borrowed. See [PackedCoproduct](struct@crate::PackedCoproduct).
+#[repr(C, packed)]
+#[derive(Copy, Clone)]
+pub union PackedUnion<A: Copy, B: Copy> {
+    head: A,
+    tail: B,
+}
+
+/// Implemented on Unions of Copy types, naming their [PackedUnion].
+pub trait Pack: Copy {
+    type Packed: Copy;
+}
+
+impl<H: Copy, T: Pack> Pack for Union<H, T> {
+    type Packed = PackedUnion<H, T::Packed>;
+}
+
+impl Pack for EmptyUnion {
+    type Packed = EmptyUnion;
+}
+
 /// Trait for properly cloning Unions that are not Copy.
-pub trait IndexedClone {
+///
+/// Sealed, like [IndexedDrop].
+pub trait IndexedClone: FnTable<CloneOp> {
     /// # Safety
     /// The argument `i` must be the index of the active variant
     /// of the Union.
@@ -38,15 +73,13 @@ pub trait IndexedClone {
 
 impl<H: Clone, T: IndexedClone> IndexedClone for Union<H, T> {
     unsafe fn iclone(&self, i: u32) -> Self {
-        if i == 0 {
-            Union {
-                head: self.head.clone(),
-            }
-        } else {
-            Union {
-                tail: ManuallyDrop::new(self.tail.iclone(i - 1)),
-            }
-        }
+        let mut out = MaybeUninit::<Self>::uninit();
+        function::<Self, CloneOp>(i)(
+            self as *const Self as *const (),
+            out.as_mut_ptr() as *mut (),
+        );
+        // Only the bytes of the variant are initialized, which is fine for a union.
+        out.assume_init()
     }
 }
 
@@ -57,7 +90,10 @@ impl IndexedClone for EmptyUnion {
     }
 }
 
-pub trait IndexedDebug {
+/// Trait for formatting the active variant of a Union.
+///
+/// Sealed, like [IndexedDrop].
+pub trait IndexedDebug: FnTable<DebugOp> {
     /// # Safety
     /// The argument `i` must be the index of the active variant
     /// of the Union.
@@ -66,31 +102,73 @@ pub trait IndexedDebug {
 
 impl<H: core::fmt::Debug, T: IndexedDebug> IndexedDebug for Union<H, T> {
     unsafe fn ifmt(&self, f: &mut core::fmt::Formatter<'_>, i: u32) -> core::fmt::Result {
+        function::<Self, DebugOp>(i)(self as *const Self as *const (), f)
+    }
+}
+
+impl IndexedDebug for EmptyUnion {
+    #[inline]
+    unsafe fn ifmt(&self, _: &mut core::fmt::Formatter<'_>, _: u32) -> core::fmt::Result {
+        match *self {}
+    }
+}
+
+/// Trait for displaying the active variant of a Union.
+pub trait IndexedDisplay {
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn idisplay(&self, f: &mut core::fmt::Formatter<'_>, i: u32) -> core::fmt::Result;
+}
+
+impl<H: core::fmt::Display, T: IndexedDisplay> IndexedDisplay for Union<H, T> {
+    unsafe fn idisplay(&self, f: &mut core::fmt::Formatter<'_>, i: u32) -> core::fmt::Result {
         if i == 0 {
             self.head.fmt(f)
         } else {
-            self.tail.ifmt(f, i - 1)
+            self.tail.idisplay(f, i - 1)
         }
     }
 }
 
-impl IndexedDebug for EmptyUnion {
+impl IndexedDisplay for EmptyUnion {
     #[inline]
-    unsafe fn ifmt(&self, _: &mut core::fmt::Formatter<'_>, _: u32) -> core::fmt::Result {
+    unsafe fn idisplay(&self, _: &mut core::fmt::Formatter<'_>, _: u32) -> core::fmt::Result {
         match *self {}
     }
 }
 
-impl<H, T: IndexedDrop> IndexedDrop for Union<H, T> {
-    unsafe fn idrop(&mut self, i: u32) {
+/// Trait for getting the source of the error in the active variant of a Union.
+pub trait IndexedError: IndexedDebug + IndexedDisplay {
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn isource(&self, i: u32) -> Option<&(dyn Error + 'static)>;
+}
+
+impl<H: Error, T: IndexedError> IndexedError for Union<H, T> {
+    unsafe fn isource(&self, i: u32) -> Option<&(dyn Error + 'static)> {
         if i == 0 {
-            ManuallyDrop::drop(&mut self.head)
+            self.head.source()
         } else {
-            self.tail.idrop(i - 1)
+            self.tail.isource(i - 1)
         }
     }
 }
 
+impl IndexedError for EmptyUnion {
+    #[inline]
+    unsafe fn isource(&self, _: u32) -> Option<&(dyn Error + 'static)> {
+        match *self {}
+    }
+}
+
+impl<H, T: IndexedDrop> IndexedDrop for Union<H, T> {
+    unsafe fn idrop(&mut self, i: u32) {
+        function::<Self, DropOp>(i)(self as *mut Self as *mut ())
+    }
+}
+
 impl IndexedDrop for EmptyUnion {
     #[inline]
     unsafe fn idrop(&mut self, _: u32) {
@@ -98,9 +176,206 @@ impl IndexedDrop for EmptyUnion {
     }
 }
 
+impl<'a, H: 'a, T: IndexedRef<'a>> IndexedRef<'a> for Union<H, T> {
+    type Ref = Union<&'a H, T::Ref>;
+    type Mut = Union<&'a mut H, T::Mut>;
+
+    unsafe fn iref(&'a self, i: u32) -> Self::Ref {
+        if i == 0 {
+            Union {
+                head: ManuallyDrop::new(&*self.head),
+            }
+        } else {
+            Union {
+                tail: ManuallyDrop::new(self.tail.iref(i - 1)),
+            }
+        }
+    }
+
+    unsafe fn imut(&'a mut self, i: u32) -> Self::Mut {
+        if i == 0 {
+            Union {
+                head: ManuallyDrop::new(&mut *self.head),
+            }
+        } else {
+            Union {
+                tail: ManuallyDrop::new(self.tail.imut(i - 1)),
+            }
+        }
+    }
+}
+
+impl<'a> IndexedRef<'a> for EmptyUnion {
+    type Ref = EmptyUnion;
+    type Mut = EmptyUnion;
+
+    #[inline]
+    unsafe fn iref(&'a self, _: u32) -> Self::Ref {
+        match *self {}
+    }
+
+    #[inline]
+    unsafe fn imut(&'a mut self, _: u32) -> Self::Mut {
+        match *self {}
+    }
+}
+
+impl<F: Poly<H>, H, T: IndexedMap<F>> IndexedMap<F> for Union<H, T> {
+    type Output = Union<F::Output, T::Output>;
+
+    unsafe fn imap(self, f: &mut F, i: u32) -> Self::Output {
+        if i == 0 {
+            Union {
+                head: ManuallyDrop::new(f.call(ManuallyDrop::into_inner(self.head))),
+            }
+        } else {
+            Union {
+                tail: ManuallyDrop::new(ManuallyDrop::into_inner(self.tail).imap(f, i - 1)),
+            }
+        }
+    }
+}
+
+impl<F> IndexedMap<F> for EmptyUnion {
+    type Output = EmptyUnion;
+
+    #[inline]
+    unsafe fn imap(self, _: &mut F, _: u32) -> Self::Output {
+        match self {}
+    }
+}
+
+/// Trait for calling the function in Fs that corresponds to the active
+/// variant of the Union.
+pub trait IndexedFold<Fs, R> {
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn ifold(self, fs: Fs, i: u32) -> R;
+}
+
+impl<H, T, FH, FT, R> IndexedFold<HCons<FH, FT>, R> for Union<H, T>
+where
+    FH: FnOnce(H) -> R,
+    T: IndexedFold<FT, R>,
+{
+    unsafe fn ifold(self, fs: HCons<FH, FT>, i: u32) -> R {
+        if i == 0 {
+            (fs.head)(ManuallyDrop::into_inner(self.head))
+        } else {
+            ManuallyDrop::into_inner(self.tail).ifold(fs.tail, i - 1)
+        }
+    }
+}
+
+impl<R> IndexedFold<HNil, R> for EmptyUnion {
+    #[inline]
+    unsafe fn ifold(self, _: HNil, _: u32) -> R {
+        match self {}
+    }
+}
+
+/// Trait for converting the active variant of a Union into a common type.
+pub trait IndexedInto<R> {
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn iinto(self, i: u32) -> R;
+}
+
+impl<R, H: Into<R>, T: IndexedInto<R>> IndexedInto<R> for Union<H, T> {
+    unsafe fn iinto(self, i: u32) -> R {
+        if i == 0 {
+            ManuallyDrop::into_inner(self.head).into()
+        } else {
+            ManuallyDrop::into_inner(self.tail).iinto(i - 1)
+        }
+    }
+}
+
+impl<R> IndexedInto<R> for EmptyUnion {
+    #[inline]
+    unsafe fn iinto(self, _: u32) -> R {
+        match self {}
+    }
+}
+
+/// Trait for coercing the active variant of a Union into a trait object.
+pub trait IndexedDyn<D: ?Sized> {
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn idyn(&self, i: u32) -> &D;
+
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn idyn_mut(&mut self, i: u32) -> &mut D;
+}
+
+impl<D: ?Sized + DynFrom<H>, H, T: IndexedDyn<D>> IndexedDyn<D> for Union<H, T> {
+    unsafe fn idyn(&self, i: u32) -> &D {
+        if i == 0 {
+            D::from_ref(&self.head)
+        } else {
+            self.tail.idyn(i - 1)
+        }
+    }
+
+    unsafe fn idyn_mut(&mut self, i: u32) -> &mut D {
+        if i == 0 {
+            D::from_mut(&mut self.head)
+        } else {
+            self.tail.idyn_mut(i - 1)
+        }
+    }
+}
+
+impl<D: ?Sized> IndexedDyn<D> for EmptyUnion {
+    #[inline]
+    unsafe fn idyn(&self, _: u32) -> &D {
+        match *self {}
+    }
+
+    #[inline]
+    unsafe fn idyn_mut(&mut self, _: u32) -> &mut D {
+        match *self {}
+    }
+}
+
+/// Trait for moving the active variant of a Union into a boxed trait object.
+#[cfg(feature = "alloc")]
+pub trait IndexedDynBox<D: ?Sized>: IndexedDyn<D> {
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn ibox(self, i: u32) -> Box<D>;
+}
+
+#[cfg(feature = "alloc")]
+impl<D: ?Sized + DynFromBox<H>, H, T: IndexedDynBox<D>> IndexedDynBox<D> for Union<H, T> {
+    unsafe fn ibox(self, i: u32) -> Box<D> {
+        if i == 0 {
+            D::from_box(Box::new(ManuallyDrop::into_inner(self.head)))
+        } else {
+            ManuallyDrop::into_inner(self.tail).ibox(i - 1)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: ?Sized> IndexedDynBox<D> for EmptyUnion {
+    #[inline]
+    unsafe fn ibox(self, _: u32) -> Box<D> {
+        match self {}
+    }
+}
+
 /// PartialEq cannot be implemented for Union, since it can contain
 /// bytes that are full of garbage and shouldn't be compared.
-pub trait IndexedEq {
+///
+/// Sealed, like [IndexedDrop].
+pub trait IndexedEq: FnTable<EqOp> {
     /// # Safety
     /// The argument `i` must be the index of the active variant
     /// of the Union.
@@ -109,17 +384,98 @@ pub trait IndexedEq {
 
 impl<H: PartialEq, T: IndexedEq> IndexedEq for Union<H, T> {
     unsafe fn ieq(&self, other: &Self, i: u32) -> bool {
+        function::<Self, EqOp>(i)(
+            self as *const Self as *const (),
+            other as *const Self as *const (),
+        )
+    }
+}
+
+impl IndexedEq for EmptyUnion {
+    #[inline]
+    unsafe fn ieq(&self, _: &Self, _: u32) -> bool {
+        match *self {}
+    }
+}
+
+/// Marker for Unions where every variant is [Eq].
+pub trait IndexedTotalEq: IndexedEq {}
+
+impl<H: Eq, T: IndexedTotalEq> IndexedTotalEq for Union<H, T> {}
+
+impl IndexedTotalEq for EmptyUnion {}
+
+/// Trait for hashing the active variant of a Union.
+pub trait IndexedHash {
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of the Union.
+    unsafe fn ihash<S: Hasher>(&self, state: &mut S, i: u32);
+}
+
+impl<H: Hash, T: IndexedHash> IndexedHash for Union<H, T> {
+    unsafe fn ihash<S: Hasher>(&self, state: &mut S, i: u32) {
         if i == 0 {
-            self.head == other.head
+            self.head.hash(state)
         } else {
-            self.tail.ieq(&other.tail, i - 1)
+            self.tail.ihash(state, i - 1)
         }
     }
 }
 
-impl IndexedEq for EmptyUnion {
+impl IndexedHash for EmptyUnion {
     #[inline]
-    unsafe fn ieq(&self, _: &Self, _: u32) -> bool {
+    unsafe fn ihash<S: Hasher>(&self, _: &mut S, _: u32) {
+        match *self {}
+    }
+}
+
+/// Trait for comparing Unions that have the same active variant.
+pub trait IndexedPartialOrd: IndexedEq {
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of both Unions.
+    unsafe fn ipartial_cmp(&self, other: &Self, i: u32) -> Option<Ordering>;
+}
+
+impl<H: PartialOrd, T: IndexedPartialOrd> IndexedPartialOrd for Union<H, T> {
+    unsafe fn ipartial_cmp(&self, other: &Self, i: u32) -> Option<Ordering> {
+        if i == 0 {
+            self.head.partial_cmp(&other.head)
+        } else {
+            self.tail.ipartial_cmp(&other.tail, i - 1)
+        }
+    }
+}
+
+impl IndexedPartialOrd for EmptyUnion {
+    #[inline]
+    unsafe fn ipartial_cmp(&self, _: &Self, _: u32) -> Option<Ordering> {
+        match *self {}
+    }
+}
+
+/// Trait for comparing Unions that have the same active variant.
+pub trait IndexedOrd: IndexedPartialOrd + IndexedTotalEq {
+    /// # Safety
+    /// The argument `i` must be the index of the active variant
+    /// of both Unions.
+    unsafe fn icmp(&self, other: &Self, i: u32) -> Ordering;
+}
+
+impl<H: Ord, T: IndexedOrd> IndexedOrd for Union<H, T> {
+    unsafe fn icmp(&self, other: &Self, i: u32) -> Ordering {
+        if i == 0 {
+            self.head.cmp(&other.head)
+        } else {
+            self.tail.icmp(&other.tail, i - 1)
+        }
+    }
+}
+
+impl IndexedOrd for EmptyUnion {
+    #[inline]
+    unsafe fn icmp(&self, _: &Self, _: u32) -> Ordering {
         match *self {}
     }
 }
@@ -153,11 +509,27 @@ where
     type Pruned = Union<H, T::Pruned>;
 }
 
+/// The Union with the variant at index I replaced with Y.
+pub trait Substitute<I, Y> {
+    type Substituted: UnionAt<I, Y>;
+}
+
+impl<X, Y, Rest> Substitute<Here, Y> for Union<X, Rest> {
+    type Substituted = Union<Y, Rest>;
+}
+
+impl<I, Y, H, T> Substitute<There<I>, Y> for Union<H, T>
+where
+    T: Substitute<I, Y>,
+{
+    type Substituted = Union<H, T::Substituted>;
+}
+
 /// Changes type to ANYTHING.
 /// # Safety
 /// Only use this on repr(C) unions. The output union must be able to contain
 /// the active variant of the input union.
-pub unsafe fn union_transmute<X, Y>(x: X) -> Y {
+pub const unsafe fn union_transmute<X, Y>(x: X) -> Y {
     #[repr(C)]
     union Transmuter<A, B> {
         before: ManuallyDrop<A>,